@@ -3,7 +3,10 @@
 pub mod vesting;
 
 pub use vesting::{
-    AcademyVestingContract, ClaimEvent, GrantEvent, RevokeEvent, VestingError, VestingSchedule,
+    AcademyVestingContract, BatchEvent, ClaimEligibility, ClaimEvent, ClaimReceipt, ClaimRecord,
+    ErrorDiagnostic, GlobalConfig, GrantEvent, GrantRequest, GrantTemplate, Parameters,
+    RevokeEvent, Roles, RoundingMode, ScheduleReplay, ScheduleStatus, VestingCurve, VestingError,
+    VestingHook, VestingHookClient, VestingSchedule,
 };
 
 #[cfg(test)]
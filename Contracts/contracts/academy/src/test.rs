@@ -1,13 +1,128 @@
 #![cfg(test)]
+extern crate std;
 
-use crate::vesting::{AcademyVestingContract, AcademyVestingContractClient};
+use crate::vesting::{
+    assert_schedule_invariants, AcademyDataKey, AcademyVestingContract,
+    AcademyVestingContractClient, BatchEvent, ClaimReceipt, ClaimRecord, ErrorDiagnostic,
+    GlobalConfig, GrantRequest, GrantTemplate, LegacyVestingSchedule, Parameters, RoundingMode,
+    ScheduleStatus, VestingCurve, VestingError,
+};
 use shared::circuit_breaker::CircuitBreakerConfig;
 use soroban_sdk::token::{Client as TokenClient, StellarAssetClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, Env,
+    contract, contracterror, contractimpl, symbol_short,
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger, MockAuth,
+        MockAuthInvoke,
+    },
+    Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val,
 };
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MockReceiptError {
+    MintDisabled = 1,
+}
+
+#[contract]
+pub struct MockReceiptToken;
+
+#[contractimpl]
+impl MockReceiptToken {
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), MockReceiptError> {
+        if env.storage().instance().has(&symbol_short!("fail")) {
+            return Err(MockReceiptError::MintDisabled);
+        }
+        let balance: i128 = env.storage().persistent().get(&to).unwrap_or(0);
+        env.storage().persistent().set(&to, &(balance + amount));
+        Ok(())
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn set_failing(env: Env) {
+        env.storage().instance().set(&symbol_short!("fail"), &true);
+    }
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MockStakingError {
+    StakingClosed = 1,
+}
+
+#[contract]
+pub struct MockStaking;
+
+#[contractimpl]
+impl MockStaking {
+    pub fn stake(env: Env, staker: Address, amount: i128) -> Result<(), MockStakingError> {
+        if env.storage().instance().has(&symbol_short!("closed")) {
+            return Err(MockStakingError::StakingClosed);
+        }
+        let staked: i128 = env.storage().persistent().get(&staker).unwrap_or(0);
+        env.storage().persistent().set(&staker, &(staked + amount));
+        Ok(())
+    }
+
+    pub fn staked(env: Env, staker: Address) -> i128 {
+        env.storage().persistent().get(&staker).unwrap_or(0)
+    }
+
+    pub fn close(env: Env) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("closed"), &true);
+    }
+}
+
+#[contract]
+pub struct MockHook;
+
+#[contractimpl]
+impl MockHook {
+    pub fn on_grant(env: Env, grant_id: u64, _beneficiary: Address, _amount: i128) {
+        Self::record(&env, symbol_short!("grant"), grant_id);
+    }
+
+    pub fn on_claim(env: Env, grant_id: u64, _beneficiary: Address, _amount: i128) {
+        Self::record(&env, symbol_short!("claim"), grant_id);
+    }
+
+    pub fn on_revoke(env: Env, grant_id: u64, _beneficiary: Address, _amount: i128) {
+        Self::record(&env, symbol_short!("revoke"), grant_id);
+    }
+
+    pub fn calls(env: Env) -> soroban_sdk::Vec<(Symbol, u64)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("calls"))
+            .unwrap_or_else(|| soroban_sdk::Vec::new(&env))
+    }
+
+    fn record(env: &Env, kind: Symbol, grant_id: u64) {
+        let mut calls = Self::calls(env.clone());
+        calls.push_back((kind, grant_id));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("calls"), &calls);
+    }
+}
+
+#[contract]
+pub struct MockSixDecimalToken;
+
+#[contractimpl]
+impl MockSixDecimalToken {
+    pub fn decimals(_env: Env) -> u32 {
+        6
+    }
+}
+
 fn create_token(
     env: &Env,
     admin: &Address,
@@ -126,7 +241,7 @@ fn test_vesting_calculation_partial_and_full() {
 
     env.ledger().with_mut(|li| li.timestamp = 550);
     let partial = client.get_vested_amount(&grant_id);
-    assert!(partial >= 490 && partial <= 510);
+    assert!((490..=510).contains(&partial));
 
     env.ledger().with_mut(|li| li.timestamp = 1001);
     let full = client.get_vested_amount(&grant_id);
@@ -156,8 +271,8 @@ fn test_claim_single_semantics_prevents_double_claim() {
     env.ledger().with_mut(|li| li.timestamp = 20);
     token_admin.mint(&client.address, &1000);
 
-    let claimed = client.claim(&grant_id, &beneficiary);
-    assert_eq!(claimed, 1000);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!(receipt.amount, 1000);
     assert_eq!(token.balance(&beneficiary), 1000);
 
     let result = client.try_claim(&grant_id, &beneficiary);
@@ -257,3 +372,4144 @@ fn test_max_batch_claims_constant() {
 
     assert_eq!(client.max_batch_claims(), 25);
 }
+
+#[test]
+fn test_average_daily_accrual_over_duration() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &(10 * 86_400));
+    assert_eq!(client.average_daily_accrual(&grant_id), 100);
+
+    let half_day = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &43_200);
+    assert_eq!(client.average_daily_accrual(&half_day), 2000);
+}
+
+#[test]
+fn test_average_daily_accrual_instant_schedule_reports_full_amount() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &750, &0, &0, &0);
+    assert_eq!(client.average_daily_accrual(&grant_id), 750);
+}
+
+#[test]
+fn test_claim_routes_to_default_destination() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    let custody = Address::generate(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    token_admin.mint(&client.address, &1000);
+
+    client.set_default_claim_destination(&beneficiary, &custody);
+    assert_eq!(
+        client.get_default_claim_destination(&beneficiary),
+        Some(custody.clone())
+    );
+
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&custody), 1000);
+    assert_eq!(token.balance(&beneficiary), 0);
+    assert_eq!(token.balance(&other), 0);
+}
+
+#[test]
+fn test_claim_to_overrides_default_destination() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    let custody = Address::generate(&env);
+
+    let first = client.grant_vesting(&admin, &beneficiary, &400, &0, &0, &10);
+    let second = client.grant_vesting(&admin, &beneficiary, &600, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    token_admin.mint(&client.address, &1000);
+
+    client.set_default_claim_destination(&beneficiary, &custody);
+    client.claim_to(&first, &beneficiary, &other);
+    assert_eq!(token.balance(&other), 400);
+    assert_eq!(token.balance(&custody), 0);
+
+    client.clear_default_claim_destination(&beneficiary);
+    assert_eq!(client.get_default_claim_destination(&beneficiary), None);
+
+    client.claim(&second, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 600);
+}
+
+fn sample_config() -> GlobalConfig {
+    GlobalConfig {
+        min_claim: 50,
+        max_schedules_per_user: 2,
+        default_cooldown: 600,
+        rounding_mode: RoundingMode::Ceil,
+        commitment_cap: 10_000,
+        default_cliff_bps: 2_500,
+        pause_halts_vesting: false,
+        auto_finalize: false,
+        transfer_veto_window: 0,
+        require_collateral: false,
+        governance_timelock: 0,
+        claim_fee_bps: 0,
+        grant_cancellation_window: 0,
+        claim_on_transfer: false,
+        summarize_batches: false,
+    }
+}
+
+#[test]
+fn test_config_defaults_and_round_trip() {
+    let env = Env::default();
+    let (client, _admin, governance, _beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let defaults = client.get_config();
+    assert_eq!(defaults.min_claim, 0);
+    assert_eq!(defaults.max_schedules_per_user, 0);
+    assert_eq!(defaults.rounding_mode, RoundingMode::Floor);
+
+    client.set_config(&governance, &sample_config());
+    assert_eq!(client.get_config(), sample_config());
+}
+
+#[test]
+fn test_set_config_requires_governance() {
+    let env = Env::default();
+    let (client, admin, _governance, _beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let result = client.try_set_config(&admin, &sample_config());
+    assert!(result.is_err());
+    assert_eq!(client.get_config().min_claim, 0);
+}
+
+#[test]
+fn test_config_limits_are_enforced() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    client.set_config(&governance, &sample_config());
+
+    let small = client.grant_vesting(&admin, &beneficiary, &40, &0, &0, &100);
+    client.grant_vesting(&admin, &beneficiary, &500, &0, &0, &10);
+    let result = client.try_grant_vesting(&admin, &beneficiary, &500, &0, &0, &10);
+    assert!(result.is_err());
+
+    // 8 of 40 vested: below the minimum and not the final sweep
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    token_admin.mint(&client.address, &1000);
+    let result = client.try_claim(&small, &beneficiary);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_push_claims_skips_unclaimable_schedules() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+
+    let vested = client.grant_vesting(&admin, &beneficiary, &300, &0, &0, &10);
+    let pending = client.grant_vesting(&admin, &beneficiary, &500, &100, &50, &200);
+    let other_vested = client.grant_vesting(&admin, &other, &700, &0, &0, &10);
+    let already_claimed = client.grant_vesting(&admin, &other, &200, &0, &0, &10);
+
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    token_admin.mint(&client.address, &2000);
+    client.claim(&already_claimed, &other);
+
+    let pushed = client.push_claims(
+        &governance,
+        &soroban_sdk::vec![&env, vested, pending, other_vested, already_claimed],
+    );
+    assert_eq!(pushed, 1000);
+    assert_eq!(token.balance(&beneficiary), 300);
+    assert_eq!(token.balance(&other), 900);
+    assert!(client.get_vesting(&vested).claimed);
+    assert!(!client.get_vesting(&pending).claimed);
+    assert!(client.get_vesting(&other_vested).claimed);
+}
+
+#[test]
+fn test_push_claims_requires_governance() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &300, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    token_admin.mint(&client.address, &300);
+
+    let result = client.try_push_claims(&admin, &soroban_sdk::vec![&env, grant_id]);
+    assert!(result.is_err());
+    assert_eq!(token.balance(&beneficiary), 0);
+}
+
+#[test]
+fn test_push_and_batch_claims_reject_duplicate_ids() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    let other_grant = client.grant_vesting(&admin, &other, &1000, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    token_admin.mint(&client.address, &2000);
+
+    let repeated = soroban_sdk::vec![&env, grant_id, grant_id];
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::push_claims(env.clone(), governance.clone(), repeated.clone()),
+            Err(VestingError::DuplicateGrantId)
+        );
+    });
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::batch_claim(env.clone(), repeated.clone(), beneficiary.clone()),
+            Err(VestingError::DuplicateGrantId)
+        );
+    });
+    assert_eq!(token.balance(&beneficiary), 0);
+    assert_eq!(client.get_vesting(&grant_id).claimed_amount, 0);
+
+    // The other grantee's backing is untouched
+    assert_eq!(
+        client.push_claims(&governance, &soroban_sdk::vec![&env, grant_id, other_grant]),
+        2000
+    );
+    assert_eq!(token.balance(&beneficiary), 1000);
+    assert_eq!(token.balance(&other), 1000);
+}
+
+#[test]
+fn test_try_get_schedule_returns_none_for_corrupt_entry() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let healthy = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    let corrupted = client.grant_vesting(&admin, &beneficiary, &2000, &0, &0, &10);
+
+    env.as_contract(&client.address, || {
+        let key = symbol_short!("sched");
+        let mut raw: Map<u64, Val> = env.storage().persistent().get(&key).unwrap();
+        raw.set(
+            corrupted,
+            Bytes::from_slice(&env, &[0xde, 0xad]).into_val(&env),
+        );
+        env.storage().persistent().set(&key, &raw);
+    });
+
+    assert_eq!(client.try_get_schedule(&healthy).unwrap().amount, 1000);
+    assert!(client.try_get_schedule(&corrupted).is_none());
+    assert!(client.try_get_schedule(&99).is_none());
+}
+
+#[test]
+fn test_try_get_schedule_returns_none_for_misshapen_map_entry() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let healthy = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    let missing = client.grant_vesting(&admin, &beneficiary, &2000, &0, &0, &10);
+    let extra = client.grant_vesting(&admin, &beneficiary, &3000, &0, &0, &10);
+
+    env.as_contract(&client.address, || {
+        let key = symbol_short!("sched");
+        let mut raw: Map<u64, Val> = env.storage().persistent().get(&key).unwrap();
+
+        let mut fields =
+            Map::<Symbol, Val>::try_from_val(&env, &raw.get(missing).unwrap()).unwrap();
+        fields.remove(Symbol::new(&env, "memo"));
+        raw.set(missing, fields.into_val(&env));
+
+        let mut fields = Map::<Symbol, Val>::try_from_val(&env, &raw.get(extra).unwrap()).unwrap();
+        fields.set(Symbol::new(&env, "bonus"), 1u32.into_val(&env));
+        raw.set(extra, fields.into_val(&env));
+
+        env.storage().persistent().set(&key, &raw);
+    });
+
+    assert_eq!(client.try_get_schedule(&healthy).unwrap().amount, 1000);
+    assert!(client.try_get_schedule(&missing).is_none());
+    assert!(client.try_get_schedule(&extra).is_none());
+}
+
+#[test]
+fn test_default_cliff_applied_when_omitted() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let mut config = client.get_config();
+    config.default_cliff_bps = 2_500;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting_default_cliff(&admin, &beneficiary, &1000, &100, &4000);
+    let schedule = client.get_vesting(&grant_id);
+    assert_eq!(schedule.cliff, 1000);
+    assert_eq!(schedule.duration, 4000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1099);
+    assert_eq!(client.get_vested_amount(&grant_id), 0);
+}
+
+#[test]
+fn test_explicit_cliff_overrides_default() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let mut config = client.get_config();
+    config.default_cliff_bps = 2_500;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &100, &60, &4000);
+    assert_eq!(client.get_vesting(&grant_id).cliff, 60);
+
+    config.default_cliff_bps = 10_001;
+    assert!(client.try_set_config(&governance, &config).is_err());
+}
+
+#[test]
+fn test_debug_user_keys_reports_index_drift() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let kept = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let dropped = client.grant_vesting(&admin, &beneficiary, &200, &0, &0, &10);
+    client.grant_vesting(&admin, &other, &300, &0, &0, &10);
+
+    let report = client.debug_user_keys(&beneficiary);
+    assert_eq!(report.indexed, soroban_sdk::vec![&env, kept, dropped]);
+    assert!(report.missing.is_empty());
+
+    env.as_contract(&client.address, || {
+        let key = symbol_short!("sched");
+        let mut raw: Map<u64, Val> = env.storage().persistent().get(&key).unwrap();
+        raw.remove(dropped);
+        env.storage().persistent().set(&key, &raw);
+    });
+
+    let report = client.debug_user_keys(&beneficiary);
+    assert_eq!(report.indexed, soroban_sdk::vec![&env, kept, dropped]);
+    assert_eq!(report.missing, soroban_sdk::vec![&env, dropped]);
+    assert_eq!(
+        client.get_user_schedule_ids(&beneficiary),
+        soroban_sdk::vec![&env, kept, dropped]
+    );
+}
+
+#[test]
+fn test_pause_time_excluded_from_vesting_when_enabled() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let mut config = client.get_config();
+    config.pause_halts_vesting = true;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.pause(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.get_vested_amount(&grant_id), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    client.unpause(&admin);
+    assert_eq!(client.get_paused_seconds(), 200);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.get_vested_amount(&grant_id), 300);
+
+    let later = client.grant_vesting(&admin, &beneficiary, &1000, &500, &0, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(client.get_vested_amount(&later), 100);
+}
+
+#[test]
+fn test_pause_time_counts_toward_vesting_by_default() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.pause(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    client.unpause(&admin);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.get_vested_amount(&grant_id), 500);
+}
+
+#[test]
+fn test_claim_returns_receipt_with_cumulative_totals() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    let first = client.claim(&grant_id, &beneficiary);
+    assert_eq!(
+        first,
+        ClaimReceipt {
+            grant_id,
+            amount: 400,
+            total_claimed: 400,
+            remaining: 600,
+            fee: 0,
+            claimed_at: 400,
+        }
+    );
+    assert!(!client.get_vesting(&grant_id).claimed);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let second = client.claim(&grant_id, &beneficiary);
+    assert_eq!(second.amount, 600);
+    assert_eq!(second.total_claimed, 1000);
+    assert_eq!(second.remaining, 0);
+    assert_eq!(second.claimed_at, 1000);
+
+    let schedule = client.get_vesting(&grant_id);
+    assert!(schedule.claimed);
+    assert_eq!(schedule.claimed_amount, 1000);
+    assert_eq!(token.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_blocked_beneficiary_cannot_claim_until_unblocked() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    client.block_address(&governance, &beneficiary);
+    assert!(client.is_blocked(&beneficiary));
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+    assert_eq!(client.get_vested_amount(&grant_id), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 800);
+    client.unblock_address(&governance, &beneficiary);
+    assert!(!client.is_blocked(&beneficiary));
+
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!(receipt.amount, 800);
+    assert_eq!(token.balance(&beneficiary), 800);
+}
+
+#[test]
+fn test_only_governance_can_block() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    assert!(client.try_block_address(&admin, &beneficiary).is_err());
+    assert!(!client.is_blocked(&beneficiary));
+}
+
+fn setup_legacy_contract(env: &Env) -> (AcademyVestingContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let governance = Address::generate(env);
+    let (reward_token, _token, _token_admin) = create_token(env, &admin);
+    let contract_id = env.register_contract(None, AcademyVestingContract);
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        storage.set(&symbol_short!("init"), &true);
+        storage.set(&symbol_short!("admin"), &admin);
+        storage.set(&symbol_short!("token"), &reward_token);
+        storage.set(&symbol_short!("gov"), &governance);
+        storage.set(&symbol_short!("cnt"), &7u64);
+    });
+
+    (
+        AcademyVestingContractClient::new(env, &contract_id),
+        admin,
+        governance,
+    )
+}
+
+#[test]
+fn test_preview_migration_without_legacy_data() {
+    let env = Env::default();
+    let (client, _admin, _governance, _beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let report = client.preview_migration();
+    assert_eq!(report.current_version, 2);
+    assert_eq!(report.target_version, 2);
+    assert_eq!(report.keys_to_migrate, 0);
+    assert!(!report.legacy_admin && !report.legacy_counter);
+}
+
+#[test]
+fn test_preview_migration_reports_legacy_keys_without_writing() {
+    let env = Env::default();
+    let (client, admin, governance) = setup_legacy_contract(&env);
+
+    let report = client.preview_migration();
+    assert_eq!(report.current_version, 1);
+    assert_eq!(report.target_version, 2);
+    assert!(report.legacy_admin);
+    assert!(report.legacy_token);
+    assert!(report.legacy_governance);
+    assert!(report.legacy_counter);
+    assert_eq!(report.keys_to_migrate, 4);
+
+    assert_eq!(client.preview_migration(), report);
+    assert_eq!(client.get_storage_version(), 1);
+
+    assert_eq!(client.migrate_storage(&admin), 4);
+    assert_eq!(client.get_storage_version(), 2);
+    assert_eq!(client.preview_migration().keys_to_migrate, 0);
+
+    let (stored_admin, _token, stored_governance) = client.get_info();
+    assert_eq!(stored_admin, admin);
+    assert_eq!(stored_governance, governance);
+
+    let beneficiary = Address::generate(&env);
+    assert_eq!(
+        client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10),
+        8
+    );
+}
+
+#[test]
+fn test_migration_upgrades_v1_schedules_with_defaults() {
+    let env = Env::default();
+    let (client, admin, _governance) = setup_legacy_contract(&env);
+    let beneficiary = Address::generate(&env);
+    let legacy = |amount: i128, claimed: bool| LegacyVestingSchedule {
+        beneficiary: beneficiary.clone(),
+        amount,
+        start_time: 0,
+        cliff: 0,
+        duration: 1000,
+        claimed,
+        revoked: false,
+        revoke_time: 0,
+    };
+    env.as_contract(&client.address, || {
+        let mut schedules = Map::new(&env);
+        schedules.set(1u64, legacy(1000, false));
+        schedules.set(2u64, legacy(500, true));
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("sched"), &schedules);
+    });
+
+    assert_eq!(client.preview_migration().legacy_schedules, 2);
+
+    assert_eq!(client.migrate_storage(&admin), 6);
+    assert_eq!(client.preview_migration().legacy_schedules, 0);
+    let open = client.get_vesting(&1);
+    assert_eq!((open.amount, open.claimed_amount), (1000, 0));
+    assert_eq!(open.created_at, 0);
+    assert_eq!(open.granted_by, admin);
+    assert_eq!(open.curve, VestingCurve::Linear);
+    assert!(open.transferable && open.milestone_met);
+    assert!(client.get_vesting(&2).claimed);
+
+    assert_eq!(
+        client.get_user_schedule_ids(&beneficiary),
+        soroban_sdk::vec![&env, 1, 2]
+    );
+    assert_eq!(client.get_active_schedule_ids(), soroban_sdk::vec![&env, 1]);
+    assert_eq!(client.get_totals(), (1500, 500, 1000));
+    assert_eq!(
+        client.schedules_created_between(&0, &100),
+        soroban_sdk::vec![&env, 1, 2]
+    );
+    let audit = client.audit_indexes();
+    assert_eq!((audit.active_missing, audit.user_missing), (0, 0));
+    assert!(!audit.active_count_drift);
+}
+
+#[test]
+fn test_migrated_v1_schedules_claim_with_cumulative_receipts() {
+    let env = Env::default();
+    let (client, admin, _governance) = setup_legacy_contract(&env);
+    let beneficiary = Address::generate(&env);
+    let legacy = |amount: i128, claimed: bool| LegacyVestingSchedule {
+        beneficiary: beneficiary.clone(),
+        amount,
+        start_time: 0,
+        cliff: 0,
+        duration: 1000,
+        claimed,
+        revoked: false,
+        revoke_time: 0,
+    };
+    env.as_contract(&client.address, || {
+        let mut schedules = Map::new(&env);
+        schedules.set(1u64, legacy(1000, false));
+        schedules.set(2u64, legacy(500, true));
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("sched"), &schedules);
+    });
+    client.migrate_storage(&admin);
+    let (_admin, token, _governance) = client.get_info();
+    StellarAssetClient::new(&env, &token).mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    let receipt = client.claim(&1, &beneficiary);
+    assert_eq!(
+        (receipt.amount, receipt.total_claimed, receipt.remaining),
+        (400, 400, 600)
+    );
+
+    // v1 closed a schedule with its only claim, so nothing is left to pay out
+    let settled = client.get_vesting(&2);
+    assert_eq!(settled.claimed_amount, settled.amount);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim(env.clone(), 2, beneficiary.clone()),
+            Err(VestingError::AlreadyClaimed)
+        );
+    });
+}
+
+#[test]
+fn test_reduce_schedule_before_vesting_returns_difference_to_treasury() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&governance, &treasury);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &100, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.reduce_schedule(&governance, &grant_id, &600);
+
+    assert_eq!(client.get_vesting(&grant_id).amount, 600);
+    assert_eq!(token.balance(&treasury), 400);
+    assert_eq!(token.balance(&client.address), 600);
+}
+
+#[test]
+fn test_reduce_schedule_rejected_once_vesting_started() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    assert_eq!(client.get_treasury(), governance);
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    assert!(client
+        .try_reduce_schedule(&governance, &grant_id, &500)
+        .is_err());
+
+    let future = client.grant_vesting(&admin, &beneficiary, &1000, &100, &0, &1000);
+    assert!(client
+        .try_reduce_schedule(&governance, &future, &0)
+        .is_err());
+    assert!(client
+        .try_reduce_schedule(&governance, &future, &1000)
+        .is_err());
+    assert!(client.try_reduce_schedule(&other, &future, &500).is_err());
+    assert_eq!(token.balance(&governance), 0);
+}
+
+#[test]
+fn test_auto_finalize_removes_fully_claimed_schedule() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+
+    let mut config = client.get_config();
+    config.auto_finalize = true;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    let kept = client.grant_vesting(&admin, &beneficiary, &500, &0, &0, &1000);
+    token_admin.mint(&client.address, &1500);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.claim(&grant_id, &beneficiary);
+    assert!(client.try_get_schedule(&grant_id).is_some());
+    client.set_claim_split(
+        &grant_id,
+        &soroban_sdk::vec![&env, (beneficiary.clone(), 10_000u32)],
+    );
+    client.tick(&soroban_sdk::vec![&env, grant_id]);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(storage.has(&AcademyDataKey::ClaimHistory(grant_id)));
+        assert!(storage.has(&AcademyDataKey::LastClaimLedger(grant_id)));
+        assert!(storage.has(&AcademyDataKey::ClaimSplit(grant_id)));
+        assert!(storage.has(&AcademyDataKey::LastStatus(grant_id)));
+    });
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!(receipt.remaining, 0);
+    assert!(client.try_get_schedule(&grant_id).is_none());
+    assert_eq!(
+        client.get_user_schedule_ids(&beneficiary),
+        soroban_sdk::vec![&env, kept]
+    );
+    assert_eq!(token.balance(&beneficiary), 1000);
+
+    // No per-grant side data outlives the schedule
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        for key in [
+            AcademyDataKey::LastClaimLedger(grant_id),
+            AcademyDataKey::PendingTransfer(grant_id),
+            AcademyDataKey::VestedAtRevoke(grant_id),
+            AcademyDataKey::MilestoneOracle(grant_id),
+            AcademyDataKey::MilestoneAcceleration(grant_id),
+            AcademyDataKey::ClaimSplit(grant_id),
+            AcademyDataKey::PeriodClaims(grant_id),
+            AcademyDataKey::ClaimHistory(grant_id),
+            AcademyDataKey::LastStatus(grant_id),
+            AcademyDataKey::FundedAmount(grant_id),
+        ] {
+            assert!(!storage.has(&key));
+        }
+    });
+}
+
+#[test]
+fn test_fully_claimed_schedule_kept_until_explicit_finalize() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+
+    client.claim(&grant_id, &beneficiary);
+    assert!(client.get_vesting(&grant_id).claimed);
+
+    client.finalize_schedule(&grant_id, &beneficiary);
+    assert!(client.try_get_schedule(&grant_id).is_none());
+    assert!(client.get_user_schedule_ids(&beneficiary).is_empty());
+}
+
+#[test]
+fn test_solvency_check_fully_funded() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    client.grant_vesting(&admin, &beneficiary, &500, &0, &0, &1000);
+    token_admin.mint(&client.address, &1500);
+
+    assert_eq!(client.solvency_check(&token.address), (1500, 1500, true));
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(client.solvency_check(&token.address), (1100, 1100, true));
+}
+
+#[test]
+fn test_solvency_check_reports_shortfall() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+
+    client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &600);
+
+    assert_eq!(client.solvency_check(&token.address), (600, 1000, false));
+
+    let (unrelated, _, unrelated_admin) = create_token(&env, &other);
+    unrelated_admin.mint(&client.address, &10);
+    assert_eq!(client.solvency_check(&unrelated), (10, 0, true));
+}
+
+#[test]
+fn test_acceptance_relative_grant_starts_at_acceptance() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let early = client.grant_vesting_on_acceptance(&admin, &beneficiary, &1000, &100, &1000);
+    let late = client.grant_vesting_on_acceptance(&admin, &other, &1000, &100, &1000);
+    assert!(client.get_vesting(&early).pending_acceptance);
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    assert_eq!(client.get_vested_amount(&early), 0);
+
+    assert_eq!(client.accept_grant(&early, &beneficiary), 5000);
+    env.ledger().with_mut(|li| li.timestamp = 8000);
+    assert_eq!(client.accept_grant(&late, &other), 8000);
+
+    let schedule = client.get_vesting(&early);
+    assert!(!schedule.pending_acceptance);
+    assert_eq!(schedule.start_time, 5000);
+    assert_eq!(schedule.cliff, 100);
+    assert_eq!(schedule.duration, 1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 8550);
+    assert_eq!(client.get_vested_amount(&early), 1000);
+    assert_eq!(client.get_vested_amount(&late), 500);
+}
+
+#[test]
+fn test_accept_grant_rejects_wrong_caller_and_repeat() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting_on_acceptance(&admin, &beneficiary, &1000, &0, &1000);
+    assert!(client.try_accept_grant(&grant_id, &other).is_err());
+
+    client.accept_grant(&grant_id, &beneficiary);
+    assert!(client.try_accept_grant(&grant_id, &beneficiary).is_err());
+
+    let fixed = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    assert!(client.try_accept_grant(&fixed, &beneficiary).is_err());
+}
+
+#[test]
+fn test_transfer_all_moves_only_transferable_schedules() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    let new_wallet = Address::generate(&env);
+
+    let movable = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    let locked = client.grant_vesting(&admin, &beneficiary, &200, &0, &0, &1000);
+    let finished = client.grant_vesting(&admin, &beneficiary, &300, &0, &0, &10);
+    let also_movable = client.grant_vesting(&admin, &beneficiary, &400, &0, &0, &1000);
+    client.set_transferable(&governance, &locked, &false);
+
+    token_admin.mint(&client.address, &300);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    client.claim(&finished, &beneficiary);
+
+    let moved = client.transfer_all(&beneficiary, &new_wallet);
+    assert_eq!(moved, soroban_sdk::vec![&env, movable, also_movable]);
+
+    assert_eq!(client.get_vesting(&movable).beneficiary, new_wallet);
+    assert_eq!(client.get_vesting(&also_movable).beneficiary, new_wallet);
+    assert_eq!(client.get_vesting(&locked).beneficiary, beneficiary);
+    assert_eq!(
+        client.get_user_schedule_ids(&beneficiary),
+        soroban_sdk::vec![&env, locked, finished]
+    );
+    assert_eq!(
+        client.get_user_schedule_ids(&new_wallet),
+        soroban_sdk::vec![&env, movable, also_movable]
+    );
+}
+
+#[test]
+fn test_transfer_schedule_requires_current_beneficiary() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    assert!(client
+        .try_transfer_schedule(&grant_id, &other, &other)
+        .is_err());
+
+    client.transfer_schedule(&grant_id, &beneficiary, &other);
+    assert_eq!(client.get_vesting(&grant_id).beneficiary, other);
+}
+
+#[test]
+fn test_grant_rejects_start_before_floor() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    client.set_min_start(&governance, &1_000);
+    assert_eq!(client.get_min_start(), 1_000);
+
+    assert!(client
+        .try_grant_vesting(&admin, &beneficiary, &100, &999, &0, &100)
+        .is_err());
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &100, &1_000, &0, &100);
+    assert_eq!(client.get_vesting(&grant_id).start_time, 1_000);
+
+    let backdated = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 100,
+        start_time: 999,
+        cliff: 0,
+        duration: 100,
+    };
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::grant_with_id(env.clone(), admin.clone(), 500, backdated),
+            Err(VestingError::StartTooEarly)
+        );
+    });
+    assert!(client.try_get_vesting(&500).is_err());
+}
+
+#[test]
+fn test_start_floor_applies_to_acceptance_time() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    client.set_min_start(&governance, &1_000);
+    let grant_id = client.grant_vesting_on_acceptance(&admin, &beneficiary, &100, &0, &100);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert!(client.try_accept_grant(&grant_id, &beneficiary).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(client.accept_grant(&grant_id, &beneficiary), 1_000);
+}
+
+#[test]
+fn test_next_event_at_walks_start_cliff_and_end() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let cliffed = client.grant_vesting(&admin, &beneficiary, &1000, &100, &200, &1000);
+    assert_eq!(client.next_event_at(&cliffed), Some(100));
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert_eq!(client.next_event_at(&cliffed), Some(300));
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    assert_eq!(client.next_event_at(&cliffed), Some(1100));
+
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    assert_eq!(client.next_event_at(&cliffed), None);
+}
+
+#[test]
+fn test_next_event_at_linear_and_terminal_schedules() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+
+    let linear = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    let short = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    token_admin.mint(&client.address, &100);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(client.next_event_at(&linear), Some(1000));
+
+    client.claim(&short, &beneficiary);
+    assert_eq!(client.next_event_at(&short), None);
+}
+
+#[test]
+fn test_schedule_invariants_accept_valid_lifecycle() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(
+        assert_schedule_invariants(&client.get_vesting(&grant_id)),
+        Ok(())
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.revoke(&grant_id, &admin, &3600);
+    assert_eq!(
+        assert_schedule_invariants(&client.get_vesting(&grant_id)),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_schedule_invariants_catch_each_violation() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &100, &10, &1000);
+    let valid = client.get_vesting(&grant_id);
+    assert_eq!(assert_schedule_invariants(&valid), Ok(()));
+
+    let mut over_claimed = valid.clone();
+    over_claimed.claimed_amount = 1001;
+    assert_eq!(
+        assert_schedule_invariants(&over_claimed),
+        Err(VestingError::InvariantViolation)
+    );
+
+    let mut negative_claim = valid.clone();
+    negative_claim.claimed_amount = -1;
+    assert!(assert_schedule_invariants(&negative_claim).is_err());
+
+    let mut stale_flag = valid.clone();
+    stale_flag.claimed = true;
+    assert!(assert_schedule_invariants(&stale_flag).is_err());
+
+    let mut cliff_past_end = valid.clone();
+    cliff_past_end.cliff = 1001;
+    assert!(assert_schedule_invariants(&cliff_past_end).is_err());
+
+    let mut overflowing_end = valid.clone();
+    overflowing_end.start_time = u64::MAX;
+    assert!(assert_schedule_invariants(&overflowing_end).is_err());
+
+    let mut revoked_before_start = valid;
+    revoked_before_start.revoked = true;
+    revoked_before_start.revoke_time = 50;
+    assert_eq!(assert_schedule_invariants(&revoked_before_start), Ok(()));
+    revoked_before_start.claimed_amount = 10;
+    assert!(assert_schedule_invariants(&revoked_before_start).is_err());
+}
+
+#[test]
+fn test_beneficiary_count_tracks_distinct_addresses() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+    assert_eq!(client.get_beneficiary_count(), 0);
+
+    client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    client.grant_vesting(&admin, &other, &100, &0, &0, &10);
+    client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+
+    assert_eq!(client.get_beneficiary_count(), 2);
+}
+
+#[test]
+fn test_claim_mints_receipt_tokens_when_configured() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let receipt_id = env.register_contract(None, MockReceiptToken);
+    let receipt = MockReceiptTokenClient::new(&env, &receipt_id);
+
+    client.set_receipt_token(&governance, &Some(receipt_id.clone()));
+    assert_eq!(client.get_receipt_token(), Some(receipt_id));
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    client.claim(&grant_id, &beneficiary);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.claim(&grant_id, &beneficiary);
+
+    assert_eq!(receipt.balance(&beneficiary), 1000);
+    assert_eq!(token.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_failed_receipt_mint_does_not_block_claim() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let receipt_id = env.register_contract(None, MockReceiptToken);
+    let receipt = MockReceiptTokenClient::new(&env, &receipt_id);
+    receipt.set_failing();
+
+    client.set_receipt_token(&governance, &Some(receipt_id));
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+
+    let claimed = client.claim(&grant_id, &beneficiary);
+    assert_eq!(claimed.amount, 1000);
+    assert_eq!(token.balance(&beneficiary), 1000);
+    assert_eq!(receipt.balance(&beneficiary), 0);
+
+    client.set_receipt_token(&governance, &None);
+    assert_eq!(client.get_receipt_token(), None);
+}
+
+#[test]
+fn test_cosigned_schedule_needs_beneficiary_and_governance() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &100);
+    token_admin.mint(&client.address, &1000);
+    client.set_requires_cosign(&governance, &grant_id, &true);
+    assert!(client.get_vesting(&grant_id).requires_cosign);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+
+    // Beneficiary alone can no longer use the plain claim paths
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+    assert!(client
+        .try_batch_claim(&soroban_sdk::vec![&env, grant_id], &beneficiary)
+        .is_err());
+
+    let receipt = client.claim_cosigned(&grant_id, &beneficiary, &governance);
+    let auths = env.auths();
+    assert!(auths.iter().any(|(signer, _)| *signer == beneficiary));
+    assert!(auths.iter().any(|(signer, _)| *signer == governance));
+    assert_eq!(receipt.amount, 1000);
+    assert_eq!(token.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_time_until_fully_vested_counts_down_to_zero() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &100, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    // Before start the whole window remains
+    assert_eq!(client.time_until_fully_vested(&grant_id), 1100);
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(client.time_until_fully_vested(&grant_id), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    assert_eq!(client.time_until_fully_vested(&grant_id), 0);
+
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(client.time_until_fully_vested(&grant_id), 0);
+}
+
+#[test]
+fn test_unit_conversion_follows_six_decimal_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let reward_token = env.register_contract(None, MockSixDecimalToken);
+    let contract_id = env.register_contract(None, AcademyVestingContract);
+    let client = AcademyVestingContractClient::new(&env, &contract_id);
+    client.init(&admin, &reward_token, &governance, &default_cb_config());
+
+    assert_eq!(client.get_token_decimals(), 6);
+    assert_eq!(client.format_amount(&5_250_000), (5_250_000, 6));
+    assert_eq!(client.to_base_units(&5), 5_000_000);
+    assert_eq!(client.to_whole_tokens(&5_999_999), 5);
+}
+
+#[test]
+fn test_min_claim_in_whole_tokens_for_seven_decimal_token() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    assert_eq!(client.get_token_decimals(), 7);
+    assert_eq!(client.format_amount(&-15), (-15, 7));
+
+    let one_token = client.to_base_units(&1);
+    assert_eq!(one_token, 10_000_000);
+    assert!(client.try_to_base_units(&i128::MAX).is_err());
+
+    let mut config = client.get_config();
+    config.min_claim = one_token;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &(10 * one_token), &0, &0, &1000);
+    token_admin.mint(&client.address, &(10 * one_token));
+
+    // 50 seconds in: half a token vested, below the one-token minimum
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!(client.to_whole_tokens(&receipt.amount), 1);
+}
+
+#[test]
+fn test_claim_and_stake_forwards_tokens_to_whitelisted_staking() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let staking_id = env.register_contract(None, MockStaking);
+    let staking = MockStakingClient::new(&env, &staking_id);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 400);
+
+    // Not whitelisted yet
+    assert!(client
+        .try_claim_and_stake(&grant_id, &beneficiary, &staking_id)
+        .is_err());
+
+    client.set_staking_contract(&governance, &staking_id, &true);
+    let receipt = client.claim_and_stake(&grant_id, &beneficiary, &staking_id);
+
+    assert_eq!(receipt.amount, 400);
+    assert_eq!(token.balance(&staking_id), 400);
+    assert_eq!(staking.staked(&beneficiary), 400);
+    assert_eq!(token.balance(&beneficiary), 0);
+}
+
+#[test]
+fn test_failed_stake_reverts_claim() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let staking_id = env.register_contract(None, MockStaking);
+    let staking = MockStakingClient::new(&env, &staking_id);
+    staking.close();
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    client.set_staking_contract(&governance, &staking_id, &true);
+    env.ledger().with_mut(|li| li.timestamp = 400);
+
+    assert!(client
+        .try_claim_and_stake(&grant_id, &beneficiary, &staking_id)
+        .is_err());
+    assert_eq!(token.balance(&staking_id), 0);
+    assert_eq!(token.balance(&client.address), 1000);
+    assert_eq!(client.get_vesting(&grant_id).claimed_amount, 0);
+}
+
+#[test]
+fn test_schedule_records_granting_admin() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    assert_eq!(client.get_vesting(&grant_id).granted_by, admin);
+
+    let pending = client.grant_vesting_on_acceptance(&admin, &beneficiary, &500, &0, &100);
+    assert_eq!(client.get_vesting(&pending).granted_by, admin);
+}
+
+#[test]
+fn test_milestone_gates_vesting_until_oracle_confirms() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    let oracle = Address::generate(&env);
+
+    let grant_id = client.grant_vesting_on_milestone(
+        &admin,
+        &beneficiary,
+        &1000,
+        &0,
+        &1000,
+        &Some(oracle.clone()),
+    );
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    assert_eq!(client.get_vested_amount(&grant_id), 0);
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+
+    // Only governance or the configured oracle may confirm
+    assert!(client.try_mark_milestone(&other, &grant_id).is_err());
+    assert_eq!(client.mark_milestone(&oracle, &grant_id), 5000);
+    assert!(client.try_mark_milestone(&oracle, &grant_id).is_err());
+
+    // Accrual starts from the milestone, not the grant
+    env.ledger().with_mut(|li| li.timestamp = 5250);
+    assert_eq!(client.get_vested_amount(&grant_id), 250);
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 250);
+}
+
+#[test]
+fn test_governance_can_confirm_milestone_without_oracle() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting_on_milestone(&admin, &beneficiary, &1000, &0, &1000, &None);
+    assert_eq!(client.next_event_at(&grant_id), None);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.mark_milestone(&governance, &grant_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    assert_eq!(client.get_vested_amount(&grant_id), 1000);
+}
+
+#[test]
+fn test_get_user_schedules_paged_windows() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    for amount in 1..=5 {
+        client.grant_vesting(&admin, &beneficiary, &(amount * 100), &0, &0, &1000);
+    }
+
+    let first = client.get_user_schedules_paged(&beneficiary, &0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().amount, 100);
+    assert_eq!(first.get(1).unwrap().amount, 200);
+
+    let last = client.get_user_schedules_paged(&beneficiary, &4, &2);
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.get(0).unwrap().amount, 500);
+
+    assert_eq!(
+        client.get_user_schedules_paged(&beneficiary, &5, &2).len(),
+        0
+    );
+    assert_eq!(
+        client
+            .get_user_schedules_paged(&beneficiary, &0, &1000)
+            .len(),
+        5
+    );
+    assert_eq!(client.get_user_schedules_paged(&other, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_idempotent_grant_replays_and_prunes_expired_keys() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+    let old_key = BytesN::from_array(&env, &[1; 32]);
+    let fresh_key = BytesN::from_array(&env, &[2; 32]);
+
+    let grant_id =
+        client.grant_vesting_idempotent(&admin, &old_key, &beneficiary, &1000, &0, &0, &1000);
+    let replayed =
+        client.grant_vesting_idempotent(&admin, &old_key, &beneficiary, &1000, &0, &0, &1000);
+    assert_eq!(replayed, grant_id);
+    assert_eq!(client.get_user_schedule_ids(&beneficiary).len(), 1);
+
+    env.ledger().with_mut(|li| li.timestamp = 8 * 86_400);
+    client.grant_vesting_idempotent(&admin, &fresh_key, &beneficiary, &500, &0, &0, &1000);
+
+    let keys = soroban_sdk::vec![
+        &env,
+        old_key.clone(),
+        fresh_key,
+        BytesN::from_array(&env, &[3; 32])
+    ];
+    assert!(client.try_prune_idempotency_keys(&other, &keys).is_err());
+    assert_eq!(client.prune_idempotency_keys(&admin, &keys), 1);
+    assert_eq!(client.prune_idempotency_keys(&admin, &keys), 0);
+
+    // A pruned key may be used again
+    let regranted =
+        client.grant_vesting_idempotent(&admin, &old_key, &beneficiary, &1000, &0, &0, &1000);
+    assert_ne!(regranted, grant_id);
+}
+
+#[test]
+fn test_far_future_start_never_over_vests() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+
+    // Start + duration would overflow u64; the naive now - start would wrap
+    let start = u64::MAX - 100;
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &start, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.get_vested_amount(&grant_id), 0);
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+    assert_eq!(client.next_event_at(&grant_id), Some(start));
+
+    env.ledger().with_mut(|li| li.timestamp = start + 50);
+    assert_eq!(client.get_vested_amount(&grant_id), 50);
+}
+
+#[test]
+fn test_active_schedule_count_tracks_lifecycle() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+    assert_eq!(client.active_schedule_count(), 0);
+
+    let short = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let long = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10_000);
+    let revocable = client.grant_vesting(&admin, &other, &500, &0, &0, &10_000);
+    token_admin.mint(&client.address, &1600);
+    assert_eq!(client.active_schedule_count(), 3);
+
+    // Partial claims keep a schedule active; the completing claim does not
+    env.ledger().with_mut(|li| li.timestamp = 5);
+    client.claim(&short, &beneficiary);
+    assert_eq!(client.active_schedule_count(), 3);
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.claim(&short, &beneficiary);
+    client.claim(&long, &beneficiary);
+    assert_eq!(client.active_schedule_count(), 2);
+
+    client.revoke(&revocable, &admin, &3600);
+    assert_eq!(client.active_schedule_count(), 1);
+}
+
+#[test]
+fn test_repeat_claim_in_same_ledger_is_rejected() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    let other_grant = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &2000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 10;
+        li.timestamp = 100;
+    });
+    client.claim(&grant_id, &beneficiary);
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+
+    // Other schedules are unaffected within the same ledger
+    client.claim(&other_grant, &beneficiary);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 11;
+        li.timestamp = 105;
+    });
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 205);
+}
+
+#[test]
+fn test_hooks_receive_lifecycle_callbacks_in_order() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    let first_id = env.register_contract(None, MockHook);
+    let second_id = env.register_contract(None, MockHook);
+
+    client.add_hook(&governance, &first_id);
+    client.add_hook(&governance, &second_id);
+    assert!(client.try_add_hook(&governance, &first_id).is_err());
+    assert_eq!(
+        client.list_hooks(),
+        soroban_sdk::vec![&env, first_id.clone(), second_id.clone()]
+    );
+
+    let claimed = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let revoked = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10_000);
+    token_admin.mint(&client.address, &200);
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.claim(&claimed, &beneficiary);
+    client.revoke(&revoked, &admin, &3600);
+
+    let expected = soroban_sdk::vec![
+        &env,
+        (symbol_short!("grant"), claimed),
+        (symbol_short!("grant"), revoked),
+        (symbol_short!("claim"), claimed),
+        (symbol_short!("revoke"), revoked),
+    ];
+    assert_eq!(MockHookClient::new(&env, &first_id).calls(), expected);
+    assert_eq!(MockHookClient::new(&env, &second_id).calls(), expected);
+
+    client.remove_hook(&governance, &first_id);
+    assert_eq!(client.list_hooks(), soroban_sdk::vec![&env, second_id]);
+}
+
+#[test]
+fn test_aggregate_curve_sums_overlapping_schedules() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    client.grant_vesting(&admin, &beneficiary, &400, &500, &0, &1000);
+    client.grant_vesting(&admin, &other, &9999, &0, &0, &10);
+
+    let samples = soroban_sdk::vec![&env, 0u64, 500, 1000, 1500, 2000];
+    assert_eq!(
+        client.aggregate_curve(&beneficiary, &samples),
+        soroban_sdk::vec![&env, 0i128, 500, 1200, 1400, 1400]
+    );
+
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for t in 0..101u64 {
+        too_many.push_back(t);
+    }
+    assert!(client.try_aggregate_curve(&beneficiary, &too_many).is_err());
+}
+
+#[test]
+fn test_transfer_veto_window_queues_and_governance_vetoes() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, token_admin) = setup_contract(&env);
+    let mut config = client.get_config();
+    config.transfer_veto_window = 3600;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+
+    assert_eq!(
+        client.request_transfer(&grant_id, &beneficiary, &other),
+        3700
+    );
+    assert_eq!(client.get_vesting(&grant_id).beneficiary, beneficiary);
+
+    // Locked for claims and further requests while queued
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+    assert!(client
+        .try_request_transfer(&grant_id, &beneficiary, &other)
+        .is_err());
+    assert!(client.try_finalize_transfer(&grant_id).is_err());
+
+    client.veto_transfer(&governance, &grant_id);
+    assert_eq!(client.get_pending_transfer(&grant_id), None);
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+    assert!(client.try_finalize_transfer(&grant_id).is_err());
+    assert_eq!(client.get_vesting(&grant_id).beneficiary, beneficiary);
+    client.claim(&grant_id, &beneficiary);
+}
+
+#[test]
+fn test_queued_transfer_finalizes_after_window() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+    let mut config = client.get_config();
+    config.transfer_veto_window = 3600;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    client.transfer_schedule(&grant_id, &beneficiary, &other);
+
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.finalize_transfer(&grant_id);
+    assert_eq!(client.get_vesting(&grant_id).beneficiary, other);
+    assert!(client.try_veto_transfer(&governance, &grant_id).is_err());
+}
+
+#[test]
+fn test_missing_core_config_surfaces_typed_errors() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .remove(&AcademyDataKey::RewardToken);
+        assert_eq!(
+            AcademyVestingContract::claim(env.clone(), grant_id, beneficiary.clone()),
+            Err(VestingError::TokenNotSet)
+        );
+
+        env.storage().instance().remove(&AcademyDataKey::Governance);
+        assert_eq!(
+            AcademyVestingContract::set_config(env.clone(), governance.clone(), sample_config()),
+            Err(VestingError::GovernanceNotSet)
+        );
+        assert_eq!(
+            AcademyVestingContract::get_treasury(env.clone()),
+            Err(VestingError::GovernanceNotSet)
+        );
+
+        env.storage().instance().remove(&AcademyDataKey::Admin);
+        assert_eq!(
+            AcademyVestingContract::grant_vesting(
+                env.clone(),
+                admin.clone(),
+                beneficiary.clone(),
+                1000,
+                0,
+                0,
+                10
+            ),
+            Err(VestingError::AdminNotSet)
+        );
+    });
+}
+
+#[test]
+fn test_simulate_claim_matches_actual_claim() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &100, &1000);
+    token_admin.mint(&client.address, &1000);
+    assert_eq!(client.simulate_claim(&grant_id), (0, 1000));
+
+    env.ledger().with_mut(|li| li.timestamp = 550);
+    let (preview, left) = client.simulate_claim(&grant_id);
+    assert_eq!(token.balance(&beneficiary), 0);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!((receipt.amount, receipt.remaining), (preview, left));
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(client.simulate_claim(&grant_id), (500, 0));
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!((receipt.amount, receipt.remaining), (500, 0));
+}
+
+#[test]
+fn test_collateral_requirement_rejects_unfunded_grants() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    let mut config = client.get_config();
+    config.require_collateral = true;
+    client.set_config(&governance, &config);
+
+    token_admin.mint(&client.address, &1500);
+    client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+
+    // 1000 already committed, only 500 left to back new grants
+    assert!(client
+        .try_grant_vesting(&admin, &beneficiary, &600, &0, &0, &1000)
+        .is_err());
+    client.grant_vesting(&admin, &beneficiary, &500, &0, &0, &1000);
+}
+
+#[test]
+fn test_governance_rotation_respects_timelock() {
+    let env = Env::default();
+    let (client, _admin, governance, _beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+    let new_governance = Address::generate(&env);
+    let mut config = client.get_config();
+    config.governance_timelock = 86_400;
+    client.set_config(&governance, &config);
+
+    assert!(client.try_accept_governance(&new_governance).is_err());
+    assert!(client
+        .try_propose_governance(&other, &new_governance)
+        .is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(
+        client.propose_governance(&governance, &new_governance),
+        87_400
+    );
+
+    // Too early, and only the proposed address may accept
+    assert!(client.try_accept_governance(&new_governance).is_err());
+    env.ledger().with_mut(|li| li.timestamp = 87_400);
+    assert!(client.try_accept_governance(&other).is_err());
+
+    client.accept_governance(&new_governance);
+    assert_eq!(client.get_info().2, new_governance);
+    assert_eq!(client.get_pending_governance(), None);
+
+    // Old governance has lost its powers
+    assert!(client.try_set_config(&governance, &config).is_err());
+    client.set_config(&new_governance, &config);
+}
+
+#[test]
+fn test_used_tokens_records_grant_token_once() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, _token_admin) =
+        setup_contract(&env);
+    assert_eq!(client.get_used_tokens().len(), 0);
+
+    client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    client.grant_vesting(&admin, &other, &500, &0, &0, &1000);
+
+    assert_eq!(
+        client.get_used_tokens(),
+        soroban_sdk::vec![&env, token.address.clone()]
+    );
+}
+
+#[test]
+fn test_tail_share_unlocks_only_at_end() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    // 20% tail: 800 vests linearly, 200 waits for the end
+    let grant_id =
+        client.grant_vesting_with_tail(&admin, &beneficiary, &1000, &0, &0, &1000, &2_000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.get_vested_amount(&grant_id), 400);
+
+    env.ledger().with_mut(|li| li.timestamp = 999);
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 799);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!(receipt.amount, 201);
+    assert_eq!(token.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_tail_and_cliff_cannot_exceed_full_amount() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    // 50% cliff + 60% tail
+    assert!(client
+        .try_grant_vesting_with_tail(&admin, &beneficiary, &1000, &0, &500, &1000, &6_000)
+        .is_err());
+    client.grant_vesting_with_tail(&admin, &beneficiary, &1000, &0, &500, &1000, &5_000);
+}
+
+#[test]
+fn test_compact_active_index_prunes_stale_ids() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+
+    let first = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let second = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let third = client.grant_vesting(&admin, &other, &100, &0, &0, &10);
+    token_admin.mint(&client.address, &100);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    client.claim(&first, &beneficiary);
+    assert_eq!(
+        client.get_active_schedule_ids(),
+        soroban_sdk::vec![&env, second, third]
+    );
+
+    // Simulate entries left behind by an older release
+    env.as_contract(&client.address, || {
+        let stale = soroban_sdk::vec![&env, 99u64, first, second, 77u64, third];
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::ActiveIds, &stale);
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::ActiveCount, &5u64);
+    });
+
+    assert!(client.try_compact_active_index(&other).is_err());
+    assert_eq!(client.compact_active_index(&admin), 3);
+    assert_eq!(
+        client.get_active_schedule_ids(),
+        soroban_sdk::vec![&env, second, third]
+    );
+    assert_eq!(client.active_schedule_count(), 2);
+    assert!(!client.audit_indexes().active_count_drift);
+    assert_eq!(client.compact_active_index(&admin), 0);
+}
+
+#[test]
+fn test_relayer_submits_beneficiary_authorized_claim() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 300);
+
+    // Only the beneficiary's signed entry is supplied; the relayer just submits
+    env.mock_auths(&[MockAuth {
+        address: &beneficiary,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "claim",
+            args: (grant_id, beneficiary.clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.claim(&grant_id, &beneficiary);
+
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            beneficiary.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "claim"),
+                    (grant_id, beneficiary.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(token.balance(&beneficiary), 300);
+}
+
+#[test]
+fn test_migration_is_lossless_for_clean_states() {
+    let env = Env::default();
+    let (client, _admin, _governance, _beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    assert!(client.migration_is_lossless());
+
+    let (legacy, admin, _governance) = setup_legacy_contract(&env);
+    assert!(legacy.migration_is_lossless());
+    legacy.migrate_storage(&admin);
+    assert!(legacy.migration_is_lossless());
+}
+
+#[test]
+fn test_migration_is_lossy_when_v2_data_conflicts() {
+    let env = Env::default();
+    let (client, _admin, _governance) = setup_legacy_contract(&env);
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::GrantCounter, &42u64);
+    });
+
+    assert!(!client.migration_is_lossless());
+}
+
+#[test]
+fn test_claim_fee_routes_to_treasury() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&governance, &treasury);
+    let mut config = client.get_config();
+    config.claim_fee_bps = 250;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!((receipt.amount, receipt.fee), (500, 12));
+    assert_eq!(token.balance(&beneficiary), 488);
+    assert_eq!(token.balance(&treasury), 12);
+    assert_eq!(client.get_vesting(&grant_id).claimed_amount, 500);
+
+    config.claim_fee_bps = 10_001;
+    assert!(client.try_set_config(&governance, &config).is_err());
+}
+
+#[test]
+fn test_zero_claim_fee_pays_everything_to_beneficiary() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!(receipt.fee, 0);
+    assert_eq!(token.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_get_schedules_by_status_filters_each_stage() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let pending = client.grant_vesting(&admin, &beneficiary, &100, &5000, &0, &100);
+    let cliffed = client.grant_vesting(&admin, &beneficiary, &100, &900, &500, &1000);
+    let vesting = client.grant_vesting(&admin, &beneficiary, &100, &500, &0, &1000);
+    let vested = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let claimed = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let revoked = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10_000);
+    token_admin.mint(&client.address, &200);
+    client.claim(&claimed, &beneficiary);
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.revoke(&revoked, &admin, &3600);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let by = |status| client.get_schedules_by_status(&status);
+    assert_eq!(
+        by(ScheduleStatus::Pending),
+        soroban_sdk::vec![&env, pending]
+    );
+    assert_eq!(by(ScheduleStatus::Cliff), soroban_sdk::vec![&env, cliffed]);
+    assert_eq!(
+        by(ScheduleStatus::Vesting),
+        soroban_sdk::vec![&env, vesting]
+    );
+    assert_eq!(
+        by(ScheduleStatus::FullyVested),
+        soroban_sdk::vec![&env, vested]
+    );
+    assert_eq!(
+        by(ScheduleStatus::Claimed),
+        soroban_sdk::vec![&env, claimed]
+    );
+    assert_eq!(
+        by(ScheduleStatus::Revoked),
+        soroban_sdk::vec![&env, revoked]
+    );
+    assert_eq!(client.get_schedule_status(&cliffed), ScheduleStatus::Cliff);
+}
+
+#[test]
+fn test_admin_log_records_actions_and_evicts_oldest() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.set_config(&governance, &client.get_config());
+    client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+
+    let log = client.get_admin_log(&10);
+    assert_eq!(log.len(), 2);
+    let first = log.get(0).unwrap();
+    assert_eq!(first.actor, governance);
+    assert_eq!(first.action, Symbol::new(&env, "set_config"));
+    assert_eq!(first.at, 50);
+    assert_eq!(
+        log.get(1).unwrap().action,
+        Symbol::new(&env, "grant_vesting")
+    );
+    assert_eq!(client.get_admin_log(&1).get(0).unwrap().actor, admin);
+
+    // Fill past the cap; the earliest entries fall off
+    for _ in 0..100 {
+        client.set_min_start(&governance, &0);
+    }
+    let log = client.get_admin_log(&500);
+    assert_eq!(log.len(), 100);
+    assert!(log
+        .iter()
+        .all(|entry| entry.action == Symbol::new(&env, "set_min_start")));
+}
+
+#[test]
+fn test_zero_duration_schedule_vests_in_full_at_start() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &100, &0, &0);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 99);
+    assert_eq!(client.get_vested_amount(&grant_id), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.get_vested_amount(&grant_id), 1000);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!((receipt.amount, receipt.remaining), (1000, 0));
+    assert_eq!(token.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_user_next_unlock_takes_earliest_event() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+    assert_eq!(client.user_next_unlock(&beneficiary), None);
+
+    let short = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    client.grant_vesting(&admin, &beneficiary, &1000, &0, &600, &2000);
+    client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1500);
+    client.grant_vesting(&admin, &other, &1000, &0, &0, &50);
+    token_admin.mint(&client.address, &100);
+
+    assert_eq!(client.user_next_unlock(&beneficiary), Some(10));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.claim(&short, &beneficiary);
+    assert_eq!(client.user_next_unlock(&beneficiary), Some(600));
+
+    env.ledger().with_mut(|li| li.timestamp = 700);
+    assert_eq!(client.user_next_unlock(&beneficiary), Some(1500));
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    assert_eq!(client.user_next_unlock(&beneficiary), None);
+}
+
+#[test]
+fn test_revoke_before_start_returns_full_amount_to_treasury() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&governance, &treasury);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &10_000, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    // No timelock wait is needed when nothing can have vested
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.revoke(&grant_id, &admin, &3600);
+
+    assert_eq!(token.balance(&treasury), 1000);
+    assert_eq!(token.balance(&client.address), 0);
+    let schedule = client.get_vesting(&grant_id);
+    assert!(schedule.revoked);
+    assert_eq!(schedule.claimed_amount, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 20_000);
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+}
+
+#[test]
+fn test_recompute_totals_repairs_drift() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+
+    let claimed = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    client.grant_vesting(&admin, &beneficiary, &500, &0, &0, &1000);
+    let revoked = client.grant_vesting(&admin, &other, &300, &0, &0, &10_000);
+    token_admin.mint(&client.address, &1800);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.claim(&claimed, &beneficiary);
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.revoke(&revoked, &admin, &3600);
+
+    // The revoked grant still owes the 108 it had vested
+    let expected = (1800, 400, 1208);
+    assert_eq!(client.get_totals(), expected);
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::Totals, &(1i128, 2i128, 3i128));
+    });
+    assert_eq!(client.get_totals(), (1, 2, 3));
+
+    assert!(client.try_recompute_totals(&other).is_err());
+    assert_eq!(client.recompute_totals(&admin), expected);
+    assert_eq!(client.get_totals(), expected);
+}
+
+#[test]
+fn test_personal_notifier_hears_only_own_claims() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, token_admin) = setup_contract(&env);
+    let global_id = env.register_contract(None, MockHook);
+    let personal_id = env.register_contract(None, MockHook);
+    let failing_id = env.register_contract(None, MockReceiptToken);
+    client.add_hook(&governance, &global_id);
+    client.set_personal_notifier(&beneficiary, &Some(personal_id.clone()));
+    assert_eq!(
+        client.get_personal_notifier(&beneficiary),
+        Some(personal_id.clone())
+    );
+
+    let own = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10);
+    let foreign = client.grant_vesting(&admin, &other, &100, &0, &0, &10);
+    let last = client.grant_vesting(&admin, &other, &100, &0, &0, &10);
+    token_admin.mint(&client.address, &300);
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    client.claim(&own, &beneficiary);
+    client.claim(&foreign, &other);
+
+    assert_eq!(
+        MockHookClient::new(&env, &personal_id).calls(),
+        soroban_sdk::vec![&env, (symbol_short!("claim"), own)]
+    );
+    assert_eq!(MockHookClient::new(&env, &global_id).calls().len(), 5);
+
+    // A notifier without `on_claim` fails silently
+    client.set_personal_notifier(&other, &Some(failing_id));
+    assert_eq!(client.claim(&last, &other).amount, 100);
+
+    client.set_personal_notifier(&beneficiary, &None);
+    assert_eq!(client.get_personal_notifier(&beneficiary), None);
+}
+
+#[test]
+fn test_schedules_created_between_filters_by_grant_time() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let early = client.grant_vesting(&admin, &beneficiary, &100, &100, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    let middle = client.grant_vesting(&admin, &other, &100, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    let late = client.grant_vesting(&admin, &beneficiary, &100, &5000, &0, &10);
+
+    assert_eq!(client.get_vesting(&middle).created_at, 200);
+    assert_eq!(
+        client.schedules_created_between(&100, &200),
+        soroban_sdk::vec![&env, early, middle]
+    );
+    assert_eq!(
+        client.schedules_created_between(&150, &300),
+        soroban_sdk::vec![&env, middle, late]
+    );
+    assert_eq!(client.schedules_created_between(&0, &u64::MAX).len(), 3);
+    assert!(client.schedules_created_between(&301, &1000).is_empty());
+    assert!(client.schedules_created_between(&300, &100).is_empty());
+}
+
+#[test]
+fn test_claim_after_partial_revoke_releases_vested_remainder() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10_000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.claim(&grant_id, &beneficiary);
+
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+    client.revoke(&grant_id, &admin, &3600);
+    assert_eq!(client.simulate_claim(&grant_id), (300, 600));
+
+    // Vesting stopped at the revoke; only the retained 300 is released
+    env.ledger().with_mut(|li| li.timestamp = 9000);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!((receipt.amount, receipt.total_claimed), (300, 400));
+    assert_eq!(token.balance(&beneficiary), 400);
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim(env.clone(), grant_id, beneficiary.clone()),
+            Err(VestingError::Revoked)
+        );
+    });
+}
+
+#[test]
+fn test_claiming_retained_amount_after_revoke_keeps_active_count() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+
+    let revoked = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    client.grant_vesting(&admin, &other, &1000, &0, &0, &100_000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+    client.revoke(&revoked, &admin, &3600);
+    assert_eq!(client.active_schedule_count(), 1);
+
+    // Fully vested before the revoke, so the claim releases everything and closes it out
+    let receipt = client.claim(&revoked, &beneficiary);
+    assert_eq!(receipt.amount, 1000);
+    assert_eq!(token.balance(&beneficiary), 1000);
+    assert!(client.get_vesting(&revoked).claimed);
+    assert_eq!(client.active_schedule_count(), 1);
+    assert!(!client.audit_indexes().active_count_drift);
+}
+
+#[test]
+fn test_claim_after_full_revoke_is_rejected() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &5000, &10_000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+    client.revoke(&grant_id, &admin, &3600);
+
+    env.ledger().with_mut(|li| li.timestamp = 20_000);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim(env.clone(), grant_id, beneficiary.clone()),
+            Err(VestingError::Revoked)
+        );
+    });
+    assert_eq!(token.balance(&beneficiary), 0);
+}
+
+#[test]
+fn test_effective_rate_follows_cliff_and_freeze() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let mut config = client.get_config();
+    config.pause_halts_vesting = true;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &500, &1000);
+    assert_eq!(client.effective_rate(&grant_id), 1);
+
+    // Past the cliff the remaining 800 vests over the last 400 seconds
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(client.effective_rate(&grant_id), 2);
+
+    client.pause(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 900);
+    assert_eq!(client.effective_rate(&grant_id), 0);
+
+    // The freeze pushed the end out, leaving the same 800 over 400 seconds
+    client.unpause(&admin);
+    assert_eq!(client.time_until_fully_vested(&grant_id), 400);
+    assert_eq!(client.effective_rate(&grant_id), 2);
+
+    env.ledger().with_mut(|li| li.timestamp = 1300);
+    assert_eq!(client.effective_rate(&grant_id), 0);
+}
+
+#[test]
+fn test_grant_batch_funded_by_single_transfer() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    token_admin.mint(&admin, &1500);
+
+    let request = |beneficiary: &Address, amount: i128| GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount,
+        start_time: 0,
+        cliff: 0,
+        duration: 1000,
+    };
+    let ids = client.grant_batch(
+        &admin,
+        &soroban_sdk::vec![&env, request(&beneficiary, 1000), request(&other, 500)],
+    );
+
+    let transfers = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == token.address
+                && topics
+                    .get(0)
+                    .map(|topic| Symbol::try_from_val(&env, &topic))
+                    == Some(Ok(symbol_short!("transfer")))
+        })
+        .count();
+    assert_eq!(transfers, 1);
+    assert_eq!(ids, soroban_sdk::vec![&env, 1, 2]);
+    assert_eq!(token.balance(&admin), 0);
+    assert_eq!(token.balance(&client.address), 1500);
+    assert_eq!(client.get_vesting(&2).beneficiary, other);
+}
+
+#[test]
+fn test_underfunded_grant_batch_creates_nothing() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+    token_admin.mint(&admin, &999);
+
+    let grants = soroban_sdk::vec![
+        &env,
+        GrantRequest {
+            beneficiary: beneficiary.clone(),
+            amount: 1000,
+            start_time: 0,
+            cliff: 0,
+            duration: 1000,
+        }
+    ];
+    assert!(client.try_grant_batch(&admin, &grants).is_err());
+    assert_eq!(token.balance(&admin), 999);
+    assert!(client.get_user_schedule_ids(&beneficiary).is_empty());
+}
+
+#[test]
+fn test_get_parameters_round_trips_after_set_config() {
+    let env = Env::default();
+    let (client, _admin, governance, _beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    assert_eq!(client.get_parameters().rounding_mode, RoundingMode::Floor);
+
+    let mut config = sample_config();
+    config.transfer_veto_window = 3600;
+    config.governance_timelock = 86_400;
+    config.claim_fee_bps = 150;
+    config.auto_finalize = true;
+    client.set_config(&governance, &config);
+
+    assert_eq!(
+        client.get_parameters(),
+        Parameters {
+            min_claim: 50,
+            max_schedules_per_user: 2,
+            commitment_cap: 10_000,
+            default_cooldown: 600,
+            transfer_veto_window: 3600,
+            governance_timelock: 86_400,
+            default_cliff_bps: 2_500,
+            claim_fee_bps: 150,
+            grant_cancellation_window: 0,
+            rounding_mode: RoundingMode::Ceil,
+        }
+    );
+}
+
+#[test]
+fn test_revoke_with_drained_balance_fails_cleanly() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&governance, &treasury);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &10_000, &0, &1000);
+    token_admin.mint(&client.address, &400);
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::revoke(env.clone(), grant_id, admin.clone(), 3600),
+            Err(VestingError::InsufficientContractBalance)
+        );
+    });
+    assert!(client.try_revoke(&grant_id, &admin, &3600).is_err());
+
+    let schedule = client.get_vesting(&grant_id);
+    assert!(!schedule.revoked);
+    assert_eq!(token.balance(&treasury), 0);
+    assert_eq!(client.active_schedule_count(), 1);
+}
+
+#[test]
+fn test_revoke_mid_vesting_returns_forfeited_share_to_treasury() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&governance, &treasury);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10_000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+
+    // With the 400 vested tokens still held back, the contract cannot cover the other 600
+    env.as_contract(&client.address, || {
+        token.transfer(&env.current_contract_address(), &admin, &500);
+    });
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::revoke(env.clone(), grant_id, admin.clone(), 3600),
+            Err(VestingError::InsufficientContractBalance)
+        );
+    });
+    assert!(!client.get_vesting(&grant_id).revoked);
+    assert_eq!(token.balance(&treasury), 0);
+
+    token_admin.mint(&client.address, &500);
+    client.revoke(&grant_id, &admin, &3600);
+    assert_eq!(token.balance(&treasury), 600);
+    assert_eq!(token.balance(&client.address), 400);
+
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 400);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_grant_from_template_fills_timing() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+    let cohort = GrantTemplate {
+        start_delay: 600,
+        cliff: 1000,
+        duration: 4000,
+    };
+    let bootcamp = symbol_short!("bootcamp");
+
+    assert!(client
+        .try_create_template(&other, &bootcamp, &cohort)
+        .is_err());
+    client.create_template(&governance, &bootcamp, &cohort);
+    client.create_template(&governance, &symbol_short!("mentor"), &cohort);
+    client.create_template(&governance, &bootcamp, &cohort);
+    assert_eq!(
+        client.list_templates(),
+        soroban_sdk::vec![&env, bootcamp.clone(), symbol_short!("mentor")]
+    );
+    assert_eq!(client.get_template(&bootcamp), Some(cohort));
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    let grant_id = client.grant_from_template(&admin, &bootcamp, &beneficiary, &750);
+    let schedule = client.get_vesting(&grant_id);
+    assert_eq!(schedule.amount, 750);
+    assert_eq!(
+        (schedule.start_time, schedule.cliff, schedule.duration),
+        (5600, 1000, 4000)
+    );
+
+    assert!(client
+        .try_grant_from_template(&admin, &symbol_short!("unknown"), &beneficiary, &750)
+        .is_err());
+}
+
+#[test]
+fn test_total_claimed_by_sums_partial_and_revoked_claims() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+    assert_eq!(client.total_claimed_by(&beneficiary), 0);
+
+    let first = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    let second = client.grant_vesting(&admin, &beneficiary, &2000, &0, &0, &10_000);
+    client.grant_vesting(&admin, &beneficiary, &500, &0, &0, &1000);
+    let foreign = client.grant_vesting(&admin, &other, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &4500);
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    client.claim(&first, &beneficiary);
+    client.claim(&second, &beneficiary);
+    client.claim(&foreign, &other);
+
+    // The revoked schedule keeps its vested 800, claimed after the revoke
+    env.ledger().with_mut(|li| li.timestamp = 4000);
+    client.revoke(&second, &admin, &3600);
+    client.claim(&second, &beneficiary);
+
+    assert_eq!(client.total_claimed_by(&beneficiary), 250 + 800);
+    assert_eq!(client.total_claimed_by(&other), 250);
+}
+
+#[test]
+fn test_cancel_grant_within_window_erases_it() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&governance, &treasury);
+    let mut config = client.get_config();
+    config.grant_cancellation_window = 600;
+    client.set_config(&governance, &config);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &1000, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1599);
+    client.cancel_grant(&admin, &grant_id);
+
+    assert_eq!(token.balance(&treasury), 1000);
+    assert!(client.try_get_vesting(&grant_id).is_err());
+    assert!(client.get_user_schedule_ids(&beneficiary).is_empty());
+    assert!(client.get_active_schedule_ids().is_empty());
+    assert_eq!(client.get_totals(), (0, 0, 0));
+}
+
+#[test]
+fn test_cancel_grant_after_window_is_rejected() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let mut config = client.get_config();
+    config.grant_cancellation_window = 600;
+    client.set_config(&governance, &config);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &1000, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1600);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::cancel_grant(env.clone(), admin.clone(), grant_id),
+            Err(VestingError::CancellationWindowClosed)
+        );
+    });
+    assert_eq!(token.balance(&client.address), 1000);
+
+    // Revoking is still possible
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    client.revoke(&grant_id, &admin, &3600);
+    assert!(client.get_vesting(&grant_id).revoked);
+}
+
+#[test]
+fn test_outstanding_approvals_track_allowance_lifecycle() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let hook_id = env.register_contract(None, MockHook);
+    client.add_hook(&governance, &hook_id);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.claim(&grant_id, &beneficiary);
+    assert!(client.get_outstanding_approvals().is_empty());
+
+    env.as_contract(&client.address, || {
+        token.approve(&client.address, &hook_id, &250, &1000);
+    });
+    assert_eq!(
+        client.get_outstanding_approvals(),
+        soroban_sdk::vec![&env, (token.address.clone(), hook_id.clone(), 250)]
+    );
+
+    env.as_contract(&client.address, || {
+        token.approve(&client.address, &hook_id, &0, &1000);
+    });
+    assert!(client.get_outstanding_approvals().is_empty());
+}
+
+#[test]
+fn test_claim_on_transfer_splits_vested_and_unvested() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+
+    token_admin.mint(&client.address, &2000);
+
+    let plain = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    client.transfer_schedule(&plain, &beneficiary, &other);
+    assert_eq!(token.balance(&beneficiary), 0);
+    assert_eq!(client.get_vesting(&plain).claimed_amount, 0);
+
+    let mut config = client.get_config();
+    config.claim_on_transfer = true;
+    client.set_config(&governance, &config);
+
+    let settled = client.grant_vesting(&admin, &beneficiary, &1000, &400, &0, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 800);
+    client.transfer_schedule(&settled, &beneficiary, &other);
+    assert_eq!(token.balance(&beneficiary), 400);
+
+    let schedule = client.get_vesting(&settled);
+    assert_eq!(schedule.beneficiary, other);
+    assert_eq!(schedule.claimed_amount, 400);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1400;
+        li.sequence_number += 1;
+    });
+    assert_eq!(client.claim(&settled, &other).amount, 600);
+    assert_eq!(client.claim(&plain, &other).amount, 1000);
+}
+
+#[test]
+fn test_audit_indexes_reports_each_inconsistency() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+
+    let first = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &10_000);
+    client.grant_vesting(&admin, &beneficiary, &200, &0, &0, &10_000);
+    let third = client.grant_vesting(&admin, &other, &300, &0, &0, &10_000);
+    token_admin.mint(&client.address, &300);
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.revoke(&third, &admin, &3600);
+
+    let clean = client.audit_indexes();
+    assert_eq!(clean.scanned, 3);
+    assert_eq!(clean.active_orphaned, 0);
+    assert_eq!(clean.active_missing, 0);
+    assert!(!clean.active_count_drift);
+    assert_eq!(clean.user_orphaned, 0);
+    assert_eq!(clean.user_missing, 0);
+
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        // Drop a live schedule from the active index and list the revoked one instead
+        storage.set(
+            &AcademyDataKey::ActiveIds,
+            &soroban_sdk::vec![&env, first, third],
+        );
+        // Lose grant 2 from its owner's index and claim `third` for the wrong user
+        storage.set(
+            &AcademyDataKey::UserScheduleIds(beneficiary.clone()),
+            &soroban_sdk::vec![&env, first, third],
+        );
+    });
+
+    let report = client.audit_indexes();
+    assert_eq!(report.active_orphaned, 1);
+    assert_eq!(report.active_missing, 1);
+    assert!(!report.active_count_drift);
+    assert_eq!(report.user_orphaned, 1);
+    assert_eq!(report.user_missing, 1);
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::ActiveCount, &5u64);
+    });
+    assert!(client.audit_indexes().active_count_drift);
+}
+
+#[test]
+fn test_mock_clock_drives_cliff_and_end_boundaries() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &100, &100, &1000);
+
+    client.set_mock_time(&200);
+    assert_eq!(client.get_vested_amount(&grant_id), 0);
+    // The mock clock runs ahead of the ledger; next-event math stays on the mock clock
+    assert_eq!(client.next_event_at(&grant_id), Some(1100));
+    assert_eq!(client.user_next_unlock(&beneficiary), Some(1100));
+    client.set_mock_time(&201);
+    assert_eq!(client.get_vested_amount(&grant_id), 1);
+    client.set_mock_time(&1099);
+    assert_eq!(client.get_vested_amount(&grant_id), 998);
+    client.set_mock_time(&1100);
+    assert_eq!(client.get_vested_amount(&grant_id), 1000);
+
+    client.clear_mock_time();
+    assert_eq!(env.ledger().timestamp(), 0);
+    assert_eq!(client.get_vested_amount(&grant_id), 0);
+}
+
+#[test]
+fn test_summarized_batch_claim_emits_one_batch_event() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+
+    let mut config = client.get_config();
+    config.summarize_batches = true;
+    client.set_config(&governance, &config);
+
+    let grant_one = client.grant_vesting(&admin, &beneficiary, &600, &0, &0, &10);
+    let grant_two = client.grant_vesting(&admin, &beneficiary, &900, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    token_admin.mint(&client.address, &1500);
+
+    client.batch_claim(&soroban_sdk::vec![&env, grant_one, grant_two], &beneficiary);
+
+    let events = env.events().all();
+    let topic_count = |name: Symbol| {
+        events
+            .iter()
+            .filter(|(contract, topics, _)| {
+                *contract == client.address
+                    && topics
+                        .get(0)
+                        .map(|topic| Symbol::try_from_val(&env, &topic))
+                        == Some(Ok(name.clone()))
+            })
+            .count()
+    };
+    assert_eq!(topic_count(symbol_short!("claim")), 0);
+    assert_eq!(topic_count(symbol_short!("v_claimed")), 0);
+    assert_eq!(topic_count(symbol_short!("batch")), 1);
+
+    let (_, _, data) = events.last().unwrap();
+    assert_eq!(
+        BatchEvent::try_from_val(&env, &data).unwrap(),
+        BatchEvent {
+            operation: symbol_short!("claim"),
+            count: 2,
+            total_amount: 1500,
+        }
+    );
+}
+
+#[test]
+fn test_grant_rejects_duration_below_floor() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+
+    assert_eq!(client.get_min_duration(), 0);
+    client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &0);
+
+    client.set_min_duration(&governance, &3_600);
+    assert_eq!(client.get_min_duration(), 3_600);
+
+    assert!(client
+        .try_grant_vesting(&admin, &beneficiary, &100, &0, &0, &3_599)
+        .is_err());
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &3_600);
+    assert_eq!(client.get_vesting(&grant_id).duration, 3_600);
+
+    assert!(client.try_set_min_duration(&admin, &0).is_err());
+}
+
+#[test]
+fn test_migrate_storage_records_version_history() {
+    let env = Env::default();
+    let (client, admin, _governance) = setup_legacy_contract(&env);
+    assert!(client.get_version_history().is_empty());
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.migrate_storage(&admin);
+    assert_eq!(
+        client.get_version_history(),
+        soroban_sdk::vec![&env, (2u32, 5_000u64)]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 9_000);
+    client.migrate_storage(&admin);
+    assert_eq!(client.get_version_history().len(), 1);
+}
+
+#[test]
+fn test_claim_split_pays_each_destination_with_remainder_last() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    let custody = Address::generate(&env);
+    let spending = Address::generate(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1001, &0, &0, &10);
+    token_admin.mint(&client.address, &1001);
+
+    env.as_contract(&client.address, || {
+        let bad = soroban_sdk::vec![
+            &env,
+            (custody.clone(), 7_000u32),
+            (spending.clone(), 2_000u32)
+        ];
+        assert_eq!(
+            AcademyVestingContract::set_claim_split(env.clone(), grant_id, bad),
+            Err(VestingError::InvalidSplit)
+        );
+    });
+
+    let splits = soroban_sdk::vec![
+        &env,
+        (custody.clone(), 7_000u32),
+        (spending.clone(), 3_000u32)
+    ];
+    client.set_claim_split(&grant_id, &splits);
+    assert_eq!(client.get_claim_split(&grant_id), Some(splits));
+
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    let receipt = client.claim(&grant_id, &beneficiary);
+    assert_eq!(receipt.amount, 1001);
+    // 70% of 1001 floors to 700; the last destination absorbs the remainder
+    assert_eq!(token.balance(&custody), 700);
+    assert_eq!(token.balance(&spending), 301);
+    assert_eq!(token.balance(&beneficiary), 0);
+
+    let claim_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == client.address
+                && topics
+                    .get(0)
+                    .map(|topic| Symbol::try_from_val(&env, &topic))
+                    == Some(Ok(symbol_short!("claim")))
+        })
+        .count();
+    assert_eq!(claim_events, 2);
+
+    let other_grant = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    client.set_claim_split(
+        &other_grant,
+        &soroban_sdk::vec![&env, (custody.clone(), 10_000u32)],
+    );
+    client.transfer_schedule(&other_grant, &beneficiary, &other);
+    assert_eq!(client.get_claim_split(&other_grant), None);
+}
+
+#[test]
+fn test_batch_and_push_claims_honor_claim_splits() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    let custody = Address::generate(&env);
+    let spending = Address::generate(&env);
+    let splits = soroban_sdk::vec![
+        &env,
+        (custody.clone(), 6_000u32),
+        (spending.clone(), 4_000u32)
+    ];
+
+    let batched = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10);
+    let pushed = client.grant_vesting(&admin, &beneficiary, &500, &0, &0, &10);
+    client.set_claim_split(&batched, &splits);
+    client.set_claim_split(&pushed, &splits);
+    token_admin.mint(&client.address, &1500);
+
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    assert_eq!(
+        client.batch_claim(&soroban_sdk::vec![&env, batched], &beneficiary),
+        1000
+    );
+    assert_eq!(token.balance(&custody), 600);
+    assert_eq!(token.balance(&spending), 400);
+
+    assert_eq!(
+        client.push_claims(&governance, &soroban_sdk::vec![&env, pushed]),
+        500
+    );
+    assert_eq!(token.balance(&custody), 900);
+    assert_eq!(token.balance(&spending), 600);
+    assert_eq!(token.balance(&beneficiary), 0);
+}
+
+#[test]
+fn test_period_cap_limits_claims_until_period_rolls() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    client.set_period_cap(&governance, &grant_id, &100, &500);
+    assert!(client
+        .try_set_period_cap(&governance, &grant_id, &100, &0)
+        .is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 100);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_099;
+        li.sequence_number += 1;
+    });
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim(env.clone(), grant_id, beneficiary.clone()),
+            Err(VestingError::PeriodCapExceeded)
+        );
+    });
+
+    env.ledger().with_mut(|li| li.timestamp = 1_100);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 100);
+    assert_eq!(token.balance(&beneficiary), 200);
+
+    client.set_period_cap(&governance, &grant_id, &0, &0);
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 800);
+}
+
+#[test]
+fn test_rescale_all_preserves_vested_amounts() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let started = client.grant_vesting(&admin, &beneficiary, &1000, &1000, &0, &1000);
+    let future = client.grant_vesting(&admin, &other, &1000, &5000, &200, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 1400);
+    assert_eq!(client.get_vested_amount(&started), 400);
+
+    // Stretch to twice the remaining time
+    assert_eq!(client.rescale_all(&governance, &20_000), 2);
+    let schedule = client.get_vesting(&started);
+    assert_eq!(schedule.start_time + schedule.duration, 2600);
+    assert_eq!(client.get_vested_amount(&started), 400);
+    let pending = client.get_vesting(&future);
+    assert_eq!(
+        (pending.start_time, pending.cliff, pending.duration),
+        (5000, 400, 2000)
+    );
+
+    // Compress back to half
+    client.rescale_all(&governance, &5_000);
+    let schedule = client.get_vesting(&started);
+    assert_eq!(schedule.start_time + schedule.duration, 2000);
+    assert_eq!(client.get_vested_amount(&started), 400);
+    env.ledger().with_mut(|li| li.timestamp = 1700);
+    assert_eq!(client.get_vested_amount(&started), 700);
+
+    assert!(client.try_rescale_all(&governance, &0).is_err());
+    assert!(client.try_rescale_all(&admin, &10_000).is_err());
+}
+
+#[test]
+fn test_schedule_data_blob_set_read_and_updated() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 100,
+        start_time: 0,
+        cliff: 0,
+        duration: 1000,
+    };
+
+    let plain = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    assert!(client.get_vesting(&plain).data.is_empty());
+
+    let data = Bytes::from_slice(&env, b"{\"course\":\"rust-101\"}");
+    let grant_id = client.grant_with_data(&admin, &request, &data);
+    assert_eq!(client.get_vesting(&grant_id).data, data);
+
+    let updated = Bytes::from_slice(&env, b"{\"course\":\"rust-201\"}");
+    client.set_schedule_data(&grant_id, &updated);
+    assert_eq!(client.get_vesting(&grant_id).data, updated);
+
+    let oversized = Bytes::from_array(&env, &[7u8; 257]);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::set_schedule_data(env.clone(), grant_id, oversized.clone()),
+            Err(VestingError::DataTooLarge)
+        );
+    });
+    assert!(client
+        .try_grant_with_data(&admin, &request, &oversized)
+        .is_err());
+    client.set_schedule_data(&grant_id, &Bytes::from_array(&env, &[7u8; 256]));
+}
+
+#[test]
+fn test_grant_to_contract_itself_is_rejected() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let contract = client.address.clone();
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::grant_vesting(
+                env.clone(),
+                admin.clone(),
+                contract.clone(),
+                100,
+                0,
+                0,
+                1000
+            ),
+            Err(VestingError::InvalidBeneficiary)
+        );
+    });
+    assert!(client
+        .try_grant_vesting(&admin, &contract, &100, &0, &0, &1000)
+        .is_err());
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    assert!(client
+        .try_transfer_schedule(&grant_id, &beneficiary, &contract)
+        .is_err());
+    assert_eq!(client.get_vesting(&grant_id).beneficiary, beneficiary);
+}
+
+#[test]
+fn test_total_reclaimed_accumulates_across_revoke_reduce_and_cancel() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &10_000);
+    assert_eq!(client.get_total_reclaimed(), 0);
+
+    // Revoked halfway: the unvested half is reclaimed
+    let revoked = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &10_000);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.revoke(&revoked, &admin, &3600);
+    assert_eq!(client.get_total_reclaimed(), 500);
+
+    // Reduced before vesting: the difference is reclaimed
+    let reduced = client.grant_vesting(&admin, &other, &800, &9_000, &0, &1000);
+    client.reduce_schedule(&governance, &reduced, &300);
+    assert_eq!(client.get_total_reclaimed(), 1_000);
+
+    // Revoked before start: the whole amount is reclaimed
+    let unstarted = client.grant_vesting(&admin, &other, &200, &9_000, &0, &1000);
+    client.revoke(&unstarted, &admin, &3600);
+    assert_eq!(client.get_total_reclaimed(), 1_200);
+
+    // Cancelled inside the window: the whole amount is reclaimed
+    let mut config = client.get_config();
+    config.grant_cancellation_window = 600;
+    client.set_config(&governance, &config);
+    let cancelled = client.grant_vesting(&admin, &other, &50, &9_000, &0, &1000);
+    client.cancel_grant(&admin, &cancelled);
+    assert_eq!(client.get_total_reclaimed(), 1_250);
+
+    // Partially funded: only what the contract holds beyond the retained share goes back
+    token_admin.mint(&admin, &700);
+    let mut request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 1000,
+        start_time: 9_000,
+        cliff: 0,
+        duration: 1000,
+    };
+    let unstarted = client.grant_partially_funded(&admin, &request, &100);
+    client.revoke(&unstarted, &admin, &3600);
+    assert_eq!(client.get_total_reclaimed(), 1_350);
+
+    request.start_time = 0;
+    request.duration = 10_000;
+    let midway = client.grant_partially_funded(&admin, &request, &600);
+    let treasury_before = token.balance(&governance);
+    client.revoke(&midway, &admin, &3600);
+    assert_eq!(token.balance(&governance) - treasury_before, 100);
+    assert_eq!(client.get_total_reclaimed(), 1_450);
+}
+
+/// Live-until ledger of one of the contract's persistent entries, read from host storage
+fn persistent_live_until(env: &Env, contract: &Address, key: Val) -> Option<u32> {
+    use soroban_sdk::xdr;
+
+    let key = std::rc::Rc::new(xdr::LedgerKey::ContractData(xdr::LedgerKeyContractData {
+        contract: xdr::ScAddress::try_from(contract).unwrap(),
+        key: xdr::ScVal::try_from_val(env, &key).unwrap(),
+        durability: xdr::ContractDataDurability::Persistent,
+    }));
+    let budget = env.host().budget_cloned();
+    env.host()
+        .with_mut_storage(|storage| {
+            Ok(storage
+                .map
+                .get::<std::rc::Rc<xdr::LedgerKey>>(&key, &budget)?
+                .and_then(|entry| entry.as_ref().and_then(|(_, live_until)| *live_until)))
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_bump_all_ttls_extends_processed_chunk() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+    let custody = Address::generate(&env);
+
+    for (grant, holder) in [(1u64, &beneficiary), (2u64, &other)] {
+        client.grant_vesting(&admin, holder, &100, &0, &0, &1000);
+        client.set_claim_split(
+            &grant,
+            &soroban_sdk::vec![&env, (custody.clone(), 10_000u32)],
+        );
+    }
+
+    let extended = env.ledger().sequence() + 180 * 17_280;
+    let split_ttl = |grant_id: u64| {
+        persistent_live_until(
+            &env,
+            &client.address,
+            AcademyDataKey::ClaimSplit(grant_id).into_val(&env),
+        )
+    };
+    assert!(split_ttl(1) < Some(extended));
+
+    assert_eq!(client.bump_all_ttls(&0, &1), 2);
+    assert_eq!(split_ttl(1), Some(extended));
+    assert!(split_ttl(2) < Some(extended));
+    assert_eq!(
+        persistent_live_until(&env, &client.address, symbol_short!("sched").into_val(&env)),
+        Some(extended)
+    );
+    assert_eq!(
+        persistent_live_until(
+            &env,
+            &client.address,
+            AcademyDataKey::UserScheduleIds(beneficiary.clone()).into_val(&env)
+        ),
+        Some(extended)
+    );
+
+    assert_eq!(client.bump_all_ttls(&2, &1), 0);
+    assert_eq!(split_ttl(2), Some(extended));
+    assert_eq!(client.bump_all_ttls(&3, &10), 0);
+}
+
+#[test]
+fn test_below_minimum_claim_publishes_diagnostic() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+
+    let mut config = client.get_config();
+    config.min_claim = 50;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    env.ledger().with_mut(|li| li.timestamp = 10);
+
+    assert!(client.try_claim(&grant_id, &beneficiary).is_err());
+
+    let diagnostics: std::vec::Vec<(u32, ErrorDiagnostic)> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == client.address
+                && topics
+                    .get(0)
+                    .map(|topic| Symbol::try_from_val(&env, &topic))
+                    == Some(Ok(symbol_short!("diag")))
+        })
+        .map(|(_, topics, data)| {
+            (
+                u32::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
+                ErrorDiagnostic::try_from_val(&env, &data).unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        diagnostics,
+        std::vec![(
+            VestingError::BelowMinimumClaim as u32,
+            ErrorDiagnostic {
+                grant_id,
+                required: 50,
+                available: 10,
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_get_all_schedule_ids_paged_skips_deleted_schedules() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &1000);
+
+    let mut config = client.get_config();
+    config.grant_cancellation_window = 600;
+    client.set_config(&governance, &config);
+
+    for _ in 0..5 {
+        client.grant_vesting(&admin, &beneficiary, &100, &5_000, &0, &1000);
+    }
+    client.grant_vesting(&admin, &other, &100, &0, &0, &1000);
+    client.cancel_grant(&admin, &2);
+    client.cancel_grant(&admin, &4);
+    client.revoke(&5, &admin, &3600);
+
+    assert_eq!(
+        client.get_all_schedule_ids_paged(&0, &3),
+        soroban_sdk::vec![&env, 1, 3]
+    );
+    assert_eq!(
+        client.get_all_schedule_ids_paged(&4, &3),
+        soroban_sdk::vec![&env, 5, 6]
+    );
+    assert!(client.get_all_schedule_ids_paged(&7, &3).is_empty());
+    assert_eq!(client.get_all_schedule_ids_paged(&1, &500).len(), 4);
+}
+
+#[test]
+fn test_total_accrued_liability_grows_with_time() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+    token_admin.mint(&client.address, &3000);
+
+    let first = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    client.grant_vesting(&admin, &other, &2000, &500, &0, &1000);
+    assert_eq!(client.total_accrued_liability(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(client.total_accrued_liability(), 400);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.total_accrued_liability(), 1000 + 1000);
+
+    client.claim(&first, &beneficiary);
+    assert_eq!(client.total_accrued_liability(), 1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(client.total_accrued_liability(), 2000);
+}
+
+#[test]
+fn test_clone_schedule_copies_curve_from_now() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+
+    let source =
+        client.grant_vesting_with_tail(&admin, &beneficiary, &1000, &100, &200, &1000, &1_000);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+
+    let cloned = client.clone_schedule(&admin, &source, &other, &3000);
+    let original = client.get_vesting(&source);
+    let copy = client.get_vesting(&cloned);
+    assert_eq!(copy.beneficiary, other);
+    assert_eq!(copy.amount, 3000);
+    assert_eq!(copy.start_time, 5_000);
+    assert_eq!(
+        (copy.cliff, copy.duration, copy.tail_bps),
+        (original.cliff, original.duration, original.tail_bps)
+    );
+
+    // Same shape, three times the amount, shifted to the clone's start
+    env.ledger().with_mut(|li| li.timestamp = 5_600);
+    assert_eq!(client.get_vested_amount(&cloned), 3 * 450);
+    assert!(client
+        .try_clone_schedule(&admin, &99, &other, &100)
+        .is_err());
+}
+
+#[test]
+fn test_caller_roles_for_admin_governance_and_beneficiary() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, _token_admin) =
+        setup_contract(&env);
+    client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+
+    let admin_roles = client.caller_roles(&admin);
+    assert!(admin_roles.is_admin && admin_roles.is_granter);
+    assert!(!admin_roles.is_governance && !admin_roles.has_schedules);
+
+    let governance_roles = client.caller_roles(&governance);
+    assert!(governance_roles.is_governance);
+    assert!(!governance_roles.is_admin && !governance_roles.is_granter);
+
+    let beneficiary_roles = client.caller_roles(&beneficiary);
+    assert!(beneficiary_roles.has_schedules);
+    assert!(!beneficiary_roles.is_admin && !beneficiary_roles.is_governance);
+
+    let nobody = client.caller_roles(&other);
+    assert!(!nobody.is_admin && !nobody.is_governance && !nobody.is_granter);
+    assert!(!nobody.has_schedules);
+}
+
+#[test]
+fn test_accelerating_milestone_pulls_end_in() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &1000);
+    let request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 1000,
+        start_time: 0,
+        cliff: 0,
+        duration: 1000,
+    };
+
+    let grant_id = client.grant_with_milestone_boost(&admin, &request, &500, &None);
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 250);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 400;
+        li.sequence_number += 1;
+    });
+    assert_eq!(client.mark_milestone(&governance, &grant_id), 400);
+    let schedule = client.get_vesting(&grant_id);
+    assert_eq!(schedule.start_time + schedule.duration, 500);
+    assert_eq!(schedule.claimed_amount, 250);
+    assert_eq!(client.get_vested_amount(&grant_id), 800);
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::mark_milestone(env.clone(), governance.clone(), grant_id),
+            Err(VestingError::MilestoneAlreadyMet)
+        );
+    });
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 750);
+    assert_eq!(token.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_transfers_paused_still_allows_claims() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &1000);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+
+    client.set_transfers_paused(&admin, &true);
+    assert!(client.transfers_paused());
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::transfer_schedule(
+                env.clone(),
+                grant_id,
+                beneficiary.clone(),
+                other.clone()
+            ),
+            Err(VestingError::TransfersPaused)
+        );
+    });
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::transfer_all(env.clone(), beneficiary.clone(), other.clone()),
+            Err(VestingError::TransfersPaused)
+        );
+    });
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 500);
+    assert_eq!(token.balance(&beneficiary), 500);
+    client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+
+    client.set_transfers_paused(&admin, &false);
+    assert!(!client.transfers_paused());
+    client.transfer_schedule(&grant_id, &beneficiary, &other);
+    assert_eq!(client.get_vesting(&grant_id).beneficiary, other);
+}
+
+#[test]
+fn test_top_schedules_by_outstanding_orders_largest_first() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+    token_admin.mint(&client.address, &10_000);
+
+    let small = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    let large = client.grant_vesting(&admin, &other, &5000, &0, &0, &1000);
+    let medium = client.grant_vesting(&admin, &beneficiary, &800, &0, &0, &1000);
+    let tied = client.grant_vesting(&admin, &other, &800, &0, &0, &1000);
+    let drained = client.grant_vesting(&admin, &beneficiary, &3000, &0, &0, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 900);
+    client.claim(&drained, &beneficiary);
+
+    assert_eq!(
+        client.top_schedules_by_outstanding(&3),
+        soroban_sdk::vec![&env, large, medium, tied]
+    );
+    assert_eq!(
+        client.top_schedules_by_outstanding(&10),
+        soroban_sdk::vec![&env, large, medium, tied, drained, small]
+    );
+    assert_eq!(client.top_schedules_by_outstanding(&0).len(), 0);
+}
+
+#[test]
+fn test_grant_with_id_rejects_collisions() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, token_admin) = setup_contract(&env);
+    let request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 500,
+        start_time: 0,
+        cliff: 0,
+        duration: 1000,
+    };
+
+    let first = client.grant_vesting(&admin, &other, &100, &0, &0, &1000);
+    assert_eq!(first, 1);
+    assert_eq!(client.grant_with_id(&admin, &2, &request), 2);
+    assert_eq!(
+        client.grant_with_id(&admin, &9_000_001, &request),
+        9_000_001
+    );
+    assert_eq!(client.get_vesting(&9_000_001).amount, 500);
+    assert_eq!(
+        client.get_user_schedule_ids(&beneficiary),
+        soroban_sdk::vec![&env, 2, 9_000_001]
+    );
+
+    for taken in [first, 2, 9_000_001] {
+        env.as_contract(&client.address, || {
+            assert_eq!(
+                AcademyVestingContract::grant_with_id(
+                    env.clone(),
+                    admin.clone(),
+                    taken,
+                    request.clone()
+                ),
+                Err(VestingError::ScheduleIdTaken)
+            );
+        });
+    }
+
+    // The counter path steps over the derived ID
+    assert_eq!(client.grant_vesting(&admin, &other, &100, &0, &0, &1000), 3);
+
+    // Deleted schedules do not free their IDs, for either path
+    let mut config = client.get_config();
+    config.grant_cancellation_window = 1000;
+    client.set_config(&governance, &config);
+    token_admin.mint(&client.address, &600);
+    let derived = client.grant_with_id(&admin, &7_000_000, &request);
+    client.cancel_grant(&admin, &derived);
+    client.cancel_grant(&admin, &first);
+    for issued in [first, derived] {
+        env.as_contract(&client.address, || {
+            assert_eq!(
+                AcademyVestingContract::grant_with_id(
+                    env.clone(),
+                    admin.clone(),
+                    issued,
+                    request.clone()
+                ),
+                Err(VestingError::ScheduleIdTaken)
+            );
+        });
+    }
+}
+
+#[test]
+fn test_export_import_schedule_round_trips() {
+    let env = Env::default();
+    let (source, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    let (target, target_admin, _, _, _, _, _) = setup_contract(&env);
+    token_admin.mint(&source.address, &1000);
+
+    let grant_id =
+        source.grant_vesting_with_tail(&admin, &beneficiary, &1000, &0, &100, &1000, &2000);
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    source.claim(&grant_id, &beneficiary);
+    let original = source.get_vesting(&grant_id);
+
+    let encoded = source.export_schedule(&grant_id);
+    let imported_id = target.import_schedule(&target_admin, &encoded);
+    let imported = target.get_vesting(&imported_id);
+
+    assert_eq!(imported_id, 1);
+    assert_eq!(target.export_schedule(&imported_id), encoded);
+    assert_eq!(imported.claimed_amount, original.claimed_amount);
+    assert_eq!(imported.tail_bps, 2000);
+    assert_eq!(
+        target.get_vested_amount(&imported_id),
+        source.get_vested_amount(&grant_id)
+    );
+    assert_eq!(
+        target.get_user_schedule_ids(&beneficiary),
+        soroban_sdk::vec![&env, imported_id]
+    );
+    assert_eq!(target.active_schedule_count(), 1);
+}
+
+#[test]
+fn test_import_schedule_rejects_tampered_encodings() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    let (source, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let (target, target_admin, target_governance, _, _, _, _) = setup_contract(&env);
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    target.set_min_start(&target_governance, &1000);
+    target.set_min_duration(&target_governance, &500);
+
+    let grant_id = source.grant_vesting(&admin, &beneficiary, &1000, &2000, &100, &1000);
+    let original = source.get_vesting(&grant_id);
+
+    let mut tampered = std::vec::Vec::new();
+    let mut claimed_flag = original.clone();
+    claimed_flag.claimed = true;
+    tampered.push(claimed_flag);
+    let mut inverted = original.clone();
+    inverted.cliff = 5000;
+    tampered.push(inverted);
+    let mut backdated = original.clone();
+    backdated.start_time = 10;
+    tampered.push(backdated);
+    let mut short = original.clone();
+    short.duration = 100;
+    short.cliff = 0;
+    tampered.push(short);
+    let mut oversized_tail = original.clone();
+    oversized_tail.tail_bps = 20_000;
+    tampered.push(oversized_tail);
+    let mut flat_steps = original.clone();
+    flat_steps.curve = VestingCurve::Stepped(0);
+    tampered.push(flat_steps);
+
+    for schedule in tampered {
+        env.as_contract(&target.address, || {
+            assert_eq!(
+                AcademyVestingContract::import_schedule(
+                    env.clone(),
+                    target_admin.clone(),
+                    schedule.to_xdr(&env)
+                ),
+                Err(VestingError::InvalidSchedule)
+            );
+        });
+    }
+    assert!(target.get_user_schedule_ids(&beneficiary).is_empty());
+
+    assert_eq!(
+        target.import_schedule(&target_admin, &source.export_schedule(&grant_id)),
+        1
+    );
+}
+
+#[test]
+fn test_non_positive_amounts_rejected() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, _token_admin) =
+        setup_contract(&env);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+
+    for amount in [0i128, -5] {
+        env.as_contract(&client.address, || {
+            assert_eq!(
+                AcademyVestingContract::grant_vesting(
+                    env.clone(),
+                    admin.clone(),
+                    beneficiary.clone(),
+                    amount,
+                    0,
+                    0,
+                    1000
+                ),
+                Err(VestingError::InvalidAmount)
+            );
+        });
+        env.as_contract(&client.address, || {
+            let request = GrantRequest {
+                beneficiary: beneficiary.clone(),
+                amount,
+                start_time: 0,
+                cliff: 0,
+                duration: 1000,
+            };
+            assert_eq!(
+                AcademyVestingContract::grant_batch(
+                    env.clone(),
+                    admin.clone(),
+                    soroban_sdk::vec![&env, request]
+                ),
+                Err(VestingError::InvalidAmount)
+            );
+        });
+        env.as_contract(&client.address, || {
+            assert_eq!(
+                AcademyVestingContract::reduce_schedule(
+                    env.clone(),
+                    governance.clone(),
+                    grant_id,
+                    amount
+                ),
+                Err(VestingError::InvalidAmount)
+            );
+        });
+    }
+
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.get_vesting(&grant_id).amount, 1000);
+    assert_eq!(client.get_user_schedule_ids(&beneficiary).len(), 1);
+}
+
+#[test]
+fn test_claim_eligibility_reports_each_blocker() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &10_000);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &200, &1000);
+    let finished = client.grant_vesting(&admin, &other, &100, &0, &0, &100);
+
+    let cliff = client.claim_eligibility(&grant_id);
+    assert!(cliff.in_cliff && cliff.nothing_claimable && !cliff.eligible);
+
+    env.ledger().with_mut(|li| li.timestamp = 210);
+    let mut config = client.get_config();
+    config.min_claim = 50;
+    client.set_config(&governance, &config);
+    let small = client.claim_eligibility(&grant_id);
+    assert!(small.below_minimum && !small.in_cliff && !small.eligible);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert!(client.claim_eligibility(&grant_id).eligible);
+    client.block_address(&governance, &beneficiary);
+    let blocked = client.claim_eligibility(&grant_id);
+    assert!(blocked.blocked && !blocked.eligible);
+    client.unblock_address(&governance, &beneficiary);
+
+    config.default_cooldown = 600;
+    client.set_config(&governance, &config);
+    client.claim(&grant_id, &beneficiary);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 800;
+        li.sequence_number += 1;
+    });
+    let cooling = client.claim_eligibility(&grant_id);
+    assert!(cooling.cooldown_active && !cooling.nothing_claimable && !cooling.eligible);
+
+    client.claim(&finished, &other);
+    let drained = client.claim_eligibility(&finished);
+    assert!(drained.nothing_claimable && drained.cooldown_active && !drained.eligible);
+
+    client.pause(&admin);
+    assert!(client.claim_eligibility(&grant_id).paused);
+}
+
+#[test]
+fn test_admin_signer_set_requires_threshold() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let signers = soroban_sdk::vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env)
+    ];
+    let (first, second) = (signers.get(0).unwrap(), signers.get(1).unwrap());
+
+    client.set_admin_signers(&admin, &signers, &2);
+    assert_eq!(client.get_admin_signers(), Some((signers.clone(), 2)));
+    assert!(client.caller_roles(&first).is_admin);
+    assert!(!client.caller_roles(&admin).is_admin);
+
+    let lone_attempt = |caller: &Address| {
+        env.as_contract(&client.address, || {
+            AcademyVestingContract::grant_vesting(
+                env.clone(),
+                caller.clone(),
+                beneficiary.clone(),
+                100,
+                0,
+                0,
+                1000,
+            )
+        })
+    };
+    assert_eq!(
+        lone_attempt(&first),
+        Err(VestingError::AdminThresholdNotMet)
+    );
+    assert_eq!(lone_attempt(&admin), Err(VestingError::Unauthorized));
+
+    client.approve_admin_call(&second, &first, &Symbol::new(&env, "grant_vesting"));
+    let grant_id = client.grant_vesting(&first, &beneficiary, &100, &0, &0, &1000);
+    assert_eq!(client.get_vesting(&grant_id).granted_by, first);
+
+    // The approval was used up by that call
+    assert_eq!(
+        lone_attempt(&first),
+        Err(VestingError::AdminThresholdNotMet)
+    );
+
+    client.approve_admin_call(&second, &first, &Symbol::new(&env, "set_admin_signers"));
+    client.set_admin_signers(&first, &soroban_sdk::vec![&env], &0);
+    assert_eq!(client.get_admin_signers(), None);
+    client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+}
+
+#[test]
+fn test_admin_approval_only_authorizes_the_call_it_signs() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let signers = soroban_sdk::vec![&env, first.clone(), second.clone()];
+    client.set_admin_signers(&admin, &signers, &2);
+    client.approve_admin_call(&second, &first, &Symbol::new(&env, "grant_vesting"));
+
+    // An approval for a grant cannot be spent rotating the signer set
+    let rotation = soroban_sdk::vec![&env, first.clone()];
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::set_admin_signers(
+                env.clone(),
+                first.clone(),
+                rotation.clone(),
+                1
+            ),
+            Err(VestingError::AdminThresholdNotMet)
+        );
+    });
+    assert_eq!(client.get_admin_signers(), Some((signers.clone(), 2)));
+
+    // The co-signer authorizes the grant invocation itself, arguments included
+    let grant_invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "grant_vesting",
+        args: (
+            first.clone(),
+            beneficiary.clone(),
+            100i128,
+            0u64,
+            0u64,
+            1000u64,
+        )
+            .into_val(&env),
+        sub_invokes: &[],
+    };
+    env.mock_auths(&[
+        MockAuth {
+            address: &first,
+            invoke: &grant_invoke,
+        },
+        MockAuth {
+            address: &second,
+            invoke: &grant_invoke,
+        },
+    ]);
+    let grant_id = client.grant_vesting(&first, &beneficiary, &100, &0, &0, &1000);
+    assert!(env.auths().iter().any(|(signer, _)| *signer == second));
+    assert_eq!(client.get_vesting(&grant_id).granted_by, first);
+}
+
+#[test]
+fn test_is_fully_funded_flags_shortfall() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, other, _token, token_admin) =
+        setup_contract(&env);
+    let senior = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    let junior = client.grant_vesting(&admin, &other, &1000, &0, &0, &1000);
+
+    token_admin.mint(&client.address, &1500);
+    assert!(client.is_fully_funded(&senior));
+    assert!(!client.is_fully_funded(&junior));
+
+    token_admin.mint(&client.address, &500);
+    assert!(client.is_fully_funded(&junior));
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.claim(&senior, &beneficiary);
+    assert!(client.is_fully_funded(&senior));
+    assert!(client.is_fully_funded(&junior));
+}
+
+#[test]
+fn test_accruing_curve_compounds_toward_total() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let day = 86_400u64;
+    let request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 1_000_000,
+        start_time: 0,
+        cliff: 0,
+        duration: 365 * day,
+    };
+
+    // 100% APY compounded daily, normalized to unlock 1_000_000 by day 365
+    let grant_id = client.grant_accruing(&admin, &request, &10_000);
+    assert_eq!(
+        client.get_vesting(&grant_id).curve,
+        VestingCurve::Accruing(10_000)
+    );
+
+    for (days, expected) in [
+        (0, 0),
+        (1, 1_597),
+        (91, 164_886),
+        (182, 376_387),
+        (364, 995_674),
+        (365, 1_000_000),
+    ] {
+        env.ledger().with_mut(|li| li.timestamp = days * day);
+        assert_eq!(client.get_vested_amount(&grant_id), expected);
+    }
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::grant_accruing(env.clone(), admin.clone(), request.clone(), 0),
+            Err(VestingError::InvalidSchedule)
+        );
+    });
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::grant_accruing(env.clone(), admin.clone(), request, u32::MAX),
+            Err(VestingError::InvalidSchedule)
+        );
+    });
+}
+
+#[test]
+fn test_revoke_future_tranches_keeps_reached_tranches() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&governance, &treasury);
+    token_admin.mint(&client.address, &1500);
+    let request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 1000,
+        start_time: 0,
+        cliff: 0,
+        duration: 4000,
+    };
+
+    let grant_id = client.grant_stepped(&admin, &request, &4);
+    for (time, expected) in [(999, 0), (1000, 250), (2999, 500), (4000, 1000)] {
+        env.ledger().with_mut(|li| li.timestamp = time);
+        assert_eq!(client.get_vested_amount(&grant_id), expected);
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 250);
+
+    // Midway through the third tranche: the second stays owed, the last two go back
+    env.ledger().with_mut(|li| li.timestamp = 2500);
+    client.revoke_future_tranches(&grant_id, &admin);
+    assert_eq!(token.balance(&treasury), 500);
+    assert!(client.get_vesting(&grant_id).revoked);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 4000;
+        li.sequence_number += 1;
+    });
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 500);
+    assert_eq!(token.balance(&client.address), 500);
+
+    let linear = client.grant_vesting(&admin, &other, &500, &0, &0, &10_000);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::revoke_future_tranches(env.clone(), linear, admin.clone()),
+            Err(VestingError::InvalidSchedule)
+        );
+    });
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::grant_stepped(env.clone(), admin.clone(), request, 4001),
+            Err(VestingError::InvalidSchedule)
+        );
+    });
+}
+
+#[test]
+fn test_schedule_replay_matches_lifecycle() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, token_admin) =
+        setup_contract(&env);
+    token_admin.mint(&client.address, &1000);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &7200);
+
+    let fresh = client.schedule_replay(&grant_id);
+    assert_eq!(fresh.status, ScheduleStatus::Vesting);
+    assert_eq!(fresh.claims.len(), 0);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 720;
+        li.sequence_number = 10;
+    });
+    client.claim(&grant_id, &beneficiary);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1440;
+        li.sequence_number = 20;
+    });
+    client.claim(&grant_id, &beneficiary);
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.revoke(&grant_id, &admin, &3600);
+
+    let replay = client.schedule_replay(&grant_id);
+    assert_eq!(replay.grant_id, grant_id);
+    assert_eq!(replay.schedule.amount, 1000);
+    assert_eq!(replay.schedule.duration, 7200);
+    assert_eq!(replay.schedule.claimed_amount, 200);
+    assert_eq!(replay.schedule.revoke_time, 3600);
+    assert_eq!(replay.status, ScheduleStatus::Revoked);
+    assert_eq!(
+        replay.claims,
+        soroban_sdk::vec![
+            &env,
+            ClaimRecord {
+                amount: 100,
+                claimed_at: 720,
+                ledger: 10
+            },
+            ClaimRecord {
+                amount: 100,
+                claimed_at: 1440,
+                ledger: 20
+            }
+        ]
+    );
+    assert_eq!(replay.vested_at_revoke, 500);
+    assert_eq!(replay.vested_now, 500);
+    assert_eq!(client.get_claim_history(&grant_id), replay.claims);
+}
+
+#[test]
+fn test_claim_to_respects_destination_allowlist() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &1000);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    let exchange = Address::generate(&env);
+
+    client.set_allowed_destinations(
+        &governance,
+        &beneficiary,
+        &soroban_sdk::vec![&env, other.clone()],
+    );
+    assert_eq!(
+        client.get_allowed_destinations(&beneficiary),
+        soroban_sdk::vec![&env, other.clone()]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim_to(
+                env.clone(),
+                grant_id,
+                beneficiary.clone(),
+                exchange.clone()
+            ),
+            Err(VestingError::DestinationNotAllowed)
+        );
+    });
+    client.claim_to(&grant_id, &beneficiary, &other);
+    assert_eq!(token.balance(&other), 100);
+
+    // A default destination outside the list falls back to the beneficiary
+    client.set_default_claim_destination(&beneficiary, &exchange);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 300;
+        li.sequence_number += 1;
+    });
+    client.claim(&grant_id, &beneficiary);
+    assert_eq!(token.balance(&beneficiary), 200);
+    assert_eq!(token.balance(&exchange), 0);
+
+    client.set_allowed_destinations(&governance, &beneficiary, &soroban_sdk::vec![&env]);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 400;
+        li.sequence_number += 1;
+    });
+    client.claim_to(&grant_id, &beneficiary, &exchange);
+    assert_eq!(token.balance(&exchange), 100);
+}
+
+#[test]
+fn test_claim_and_stake_respects_destination_allowlist() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    let staking_id = env.register_contract(None, MockStaking);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &1000);
+    token_admin.mint(&client.address, &1000);
+    client.set_staking_contract(&governance, &staking_id, &true);
+    client.set_allowed_destinations(
+        &governance,
+        &beneficiary,
+        &soroban_sdk::vec![&env, other.clone()],
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim_and_stake(
+                env.clone(),
+                grant_id,
+                beneficiary.clone(),
+                staking_id.clone()
+            ),
+            Err(VestingError::DestinationNotAllowed)
+        );
+    });
+    assert_eq!(token.balance(&staking_id), 0);
+
+    client.set_allowed_destinations(
+        &governance,
+        &beneficiary,
+        &soroban_sdk::vec![&env, other.clone(), staking_id.clone()],
+    );
+    client.claim_and_stake(&grant_id, &beneficiary, &staking_id);
+    assert_eq!(token.balance(&staking_id), 400);
+}
+
+#[test]
+fn test_tick_emits_status_transitions() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &100, &100, &1000);
+    let ids = soroban_sdk::vec![&env, grant_id, 99];
+
+    let last_transition = || {
+        let (contract, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(contract, client.address);
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()),
+            Ok(symbol_short!("status"))
+        );
+        <(u64, ScheduleStatus, ScheduleStatus)>::try_from_val(&env, &data).unwrap()
+    };
+
+    assert_eq!(client.tick(&ids), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert_eq!(client.tick(&ids), 1);
+    assert_eq!(
+        last_transition(),
+        (grant_id, ScheduleStatus::Pending, ScheduleStatus::Cliff)
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    assert_eq!(client.tick(&ids), 1);
+    assert_eq!(
+        last_transition(),
+        (grant_id, ScheduleStatus::Cliff, ScheduleStatus::Vesting)
+    );
+    assert_eq!(client.tick(&ids), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 1200);
+    assert_eq!(client.tick(&ids), 1);
+    assert_eq!(
+        last_transition(),
+        (
+            grant_id,
+            ScheduleStatus::Vesting,
+            ScheduleStatus::FullyVested
+        )
+    );
+}
+
+#[test]
+fn test_partially_funded_schedule_claims_up_to_funding() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, token, token_admin) =
+        setup_contract(&env);
+    token_admin.mint(&admin, &1000);
+    let request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 1000,
+        start_time: 0,
+        cliff: 0,
+        duration: 1000,
+    };
+
+    let grant_id = client.grant_partially_funded(&admin, &request, &300);
+    assert_eq!(client.get_funded_amount(&grant_id), Some(300));
+    assert_eq!(token.balance(&client.address), 300);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 300);
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim(env.clone(), grant_id, beneficiary.clone()),
+            Err(VestingError::InsufficientFunded)
+        );
+    });
+
+    assert_eq!(client.fund_schedule(&admin, &grant_id, &700), 1000);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 200);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number += 1;
+    });
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 500);
+    assert_eq!(token.balance(&beneficiary), 1000);
+    assert!(client.try_fund_schedule(&admin, &grant_id, &1).is_err());
+}
+
+#[test]
+fn test_revoke_cancel_and_reduce_refund_only_funded_tokens() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, token, token_admin) = setup_contract(&env);
+    let mut config = client.get_config();
+    config.grant_cancellation_window = 1000;
+    client.set_config(&governance, &config);
+    token_admin.mint(&admin, &900);
+    token_admin.mint(&client.address, &1000);
+    let backed = client.grant_vesting(&admin, &other, &1000, &0, &0, &100_000);
+    let request = GrantRequest {
+        beneficiary: beneficiary.clone(),
+        amount: 1000,
+        start_time: 10_000,
+        cliff: 0,
+        duration: 1000,
+    };
+
+    let revoked = client.grant_partially_funded(&admin, &request, &100);
+    client.revoke(&revoked, &admin, &3600);
+    assert_eq!(token.balance(&governance), 100);
+
+    let cancelled = client.grant_partially_funded(&admin, &request, &200);
+    client.cancel_grant(&admin, &cancelled);
+    assert_eq!(token.balance(&governance), 300);
+    assert_eq!(client.get_funded_amount(&cancelled), None);
+
+    let reduced = client.grant_partially_funded(&admin, &request, &600);
+    client.reduce_schedule(&governance, &reduced, &400);
+    assert_eq!(token.balance(&governance), 500);
+    assert_eq!(client.get_funded_amount(&reduced), Some(400));
+
+    // The fully backed grant and the reduced one still hold all their funding
+    assert_eq!(token.balance(&client.address), 1400);
+    assert!(client.is_fully_funded(&backed));
+}
+
+#[test]
+fn test_swap_beneficiaries_updates_both_indexes() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, other, _token, token_admin) = setup_contract(&env);
+    let mine = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    let theirs = client.grant_vesting(&admin, &other, &200, &0, &0, &1000);
+    let claimed = client.grant_vesting(&admin, &other, &300, &0, &0, &1000);
+
+    client.swap_beneficiaries(&governance, &mine, &theirs);
+    assert_eq!(client.get_vesting(&mine).beneficiary, other);
+    assert_eq!(client.get_vesting(&theirs).beneficiary, beneficiary);
+    assert_eq!(
+        client.get_user_schedule_ids(&beneficiary),
+        soroban_sdk::vec![&env, theirs]
+    );
+    assert_eq!(
+        client.get_user_schedule_ids(&other),
+        soroban_sdk::vec![&env, claimed, mine]
+    );
+
+    token_admin.mint(&client.address, &300);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.claim(&claimed, &other);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::swap_beneficiaries(
+                env.clone(),
+                governance.clone(),
+                theirs,
+                claimed
+            ),
+            Err(VestingError::AlreadyClaimed)
+        );
+    });
+}
+
+#[test]
+fn test_final_claim_sweeps_rounding_dust() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &1000);
+    let mut config = client.get_config();
+    config.min_claim = 200;
+    client.set_config(&governance, &config);
+
+    // 1000 over 7 seconds never divides evenly; the last 143 is below the minimum
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &1000, &0, &0, &7);
+    let mut total = 0;
+    for (second, expected) in [(3, 428), (6, 429), (7, 143)] {
+        env.ledger().with_mut(|li| {
+            li.timestamp = second;
+            li.sequence_number += 1;
+        });
+        let receipt = client.claim(&grant_id, &beneficiary);
+        assert_eq!(receipt.amount, expected);
+        total += receipt.amount;
+    }
+
+    assert_eq!(total, 1000);
+    assert_eq!(token.balance(&beneficiary), 1000);
+    let schedule = client.get_vesting(&grant_id);
+    assert!(schedule.claimed);
+    assert_eq!(schedule.claimed_amount, schedule.amount);
+}
+
+#[test]
+fn test_beneficiary_memo_set_read_and_updated() {
+    let env = Env::default();
+    let (client, admin, _governance, beneficiary, _other, _token, _token_admin) =
+        setup_contract(&env);
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &100, &0, &0, &1000);
+    assert_eq!(client.get_vesting(&grant_id).memo, Symbol::new(&env, ""));
+
+    client.set_memo(&grant_id, &symbol_short!("ref42"));
+    assert_eq!(env.auths()[0].0, beneficiary);
+    assert_eq!(client.get_vesting(&grant_id).memo, symbol_short!("ref42"));
+
+    let updated = Symbol::new(&env, "invoice_2026_q3");
+    client.set_memo(&grant_id, &updated);
+    assert_eq!(client.get_vesting(&grant_id).memo, updated);
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::set_memo(
+                env.clone(),
+                grant_id,
+                Symbol::new(&env, "invoice_2026_q3_final")
+            ),
+            Err(VestingError::DataTooLarge)
+        );
+    });
+    assert_eq!(client.get_vesting(&grant_id).memo, updated);
+}
+
+#[test]
+fn test_grant_below_min_claim_is_claimable_once_fully_vested() {
+    let env = Env::default();
+    let (client, admin, governance, beneficiary, _other, token, token_admin) = setup_contract(&env);
+    token_admin.mint(&client.address, &40);
+    let mut config = client.get_config();
+    config.min_claim = 100;
+    client.set_config(&governance, &config);
+
+    let grant_id = client.grant_vesting(&admin, &beneficiary, &40, &0, &0, &10);
+    env.ledger().with_mut(|li| li.timestamp = 5);
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            AcademyVestingContract::claim(env.clone(), grant_id, beneficiary.clone()),
+            Err(VestingError::BelowMinimumClaim)
+        );
+    });
+    assert!(!client.claim_eligibility(&grant_id).eligible);
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    assert!(client.claim_eligibility(&grant_id).eligible);
+    assert_eq!(client.claim(&grant_id, &beneficiary).amount, 40);
+    assert_eq!(token.balance(&beneficiary), 40);
+    assert!(client.get_vesting(&grant_id).claimed);
+}
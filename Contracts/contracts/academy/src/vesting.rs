@@ -1,7 +1,34 @@
-use shared::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
+use shared::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, PauseLevel};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short,
+    xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, SymbolStr, TryFromVal, Val, Vec,
+};
 
 const MAX_BATCH_CLAIMS: u32 = 25;
+const MAX_BATCH_GRANTS: u32 = 25;
+const MAX_PAGE_SIZE: u32 = 50;
+const MAX_HOOKS: u32 = 5;
+const MAX_CURVE_SAMPLES: u32 = 100;
+const MAX_STATUS_SCAN: u32 = 200;
+const MAX_ADMIN_LOG: u32 = 100;
+const MAX_CLAIM_SPLITS: u32 = 10;
+const MAX_SCHEDULE_DATA: u32 = 256;
+const MAX_MEMO_LEN: usize = 16;
+const MAX_TOP_SCHEDULES: u32 = 20;
+const MAX_ADMIN_SIGNERS: u32 = 10;
+const MAX_CLAIM_HISTORY: u32 = 50;
+const MAX_ALLOWED_DESTINATIONS: u32 = 20;
+const SECONDS_PER_DAY: u64 = 86_400;
+const LEDGERS_PER_DAY: u32 = 17_280;
+const TTL_BUMP_THRESHOLD: u32 = 30 * LEDGERS_PER_DAY;
+const TTL_BUMP_EXTEND_TO: u32 = 180 * LEDGERS_PER_DAY;
+const BPS_DENOMINATOR: u32 = 10_000;
+const STORAGE_VERSION: u32 = 2;
+const DEFAULT_TOKEN_DECIMALS: u32 = 7;
+const IDEMPOTENCY_WINDOW: u64 = 7 * SECONDS_PER_DAY;
+const ACCRUAL_SCALE: u128 = 1_000_000_000_000;
+const MAX_ACCRUAL_GROWTH: u128 = 1_000_000 * ACCRUAL_SCALE;
 
 /// Vesting schedule for an academy reward
 #[contracttype]
@@ -10,11 +37,70 @@ pub struct VestingSchedule {
     pub beneficiary: Address,
     pub amount: i128,
     pub start_time: u64,
-    pub cliff: u64,    // Time (in seconds) before any tokens unlock
-    pub duration: u64, // Total vesting duration (in seconds)
+    pub cliff: u64,           // Time (in seconds) before any tokens unlock
+    pub duration: u64,        // Total vesting duration (in seconds)
+    pub claimed: bool,        // Set once the full amount has been claimed
+    pub claimed_amount: i128, // Cumulative amount claimed so far (all of it if claimed under v1)
+    pub revoked: bool,
+    pub revoke_time: u64,           // When it was revoked (0 if not revoked)
+    pub pause_offset: u64,          // Cumulative paused seconds already elapsed at grant time
+    pub pending_acceptance: bool,   // Start time is set when the beneficiary accepts
+    pub transferable: bool,         // Whether the beneficiary may move it to another address
+    pub requires_cosign: bool,      // Claims also need governance authorization
+    pub granted_by: Address,        // Authorized caller that created the grant
+    pub milestone_met: bool,        // Vesting is gated until this is set (see `mark_milestone`)
+    pub tail_bps: u32,              // Share of `amount` withheld until the schedule ends
+    pub created_at: u64,            // Ledger timestamp of the grant (0 if migrated from v1)
+    pub max_claim_per_period: i128, // Most that may be claimed per rolling period (see below)
+    pub period_seconds: u64,        // Length of the claim-cap period (0 = uncapped)
+    pub data: Bytes,                // Integrator-defined payload (empty = none)
+    pub curve: VestingCurve,        // Unlock shape between the cliff and the end
+    pub memo: Symbol,               // Beneficiary-set reference note (empty = none)
+}
+
+/// A schedule as stored by the v1 contract; `migrate_storage` rewrites these in place
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LegacyVestingSchedule {
+    pub beneficiary: Address,
+    pub amount: i128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
     pub claimed: bool,
     pub revoked: bool,
-    pub revoke_time: u64, // When it was revoked (0 if not revoked)
+    pub revoke_time: u64,
+}
+
+/// Unlock shape of the non-tail amount between the cliff and the end of a schedule
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum VestingCurve {
+    Linear,
+    /// Compounds daily at this APY in bps, normalized so the full amount unlocks at the end
+    Accruing(u32),
+    /// Unlocks in this many equal tranches, spaced evenly after the cliff
+    Stepped(u32),
+}
+
+/// One schedule to create through `grant_batch`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GrantRequest {
+    pub beneficiary: Address,
+    pub amount: i128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+/// Timing shared by grants made from a named template
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantTemplate {
+    pub start_delay: u64, // Seconds after the grant before vesting starts
+    pub cliff: u64,
+    pub duration: u64,
 }
 
 /// Vesting grant event for off-chain indexing
@@ -38,9 +124,35 @@ pub struct ClaimEvent {
     pub grant_id: u64,
     pub beneficiary: Address,
     pub amount: i128,
+    pub fee: i128, // Part of `amount` routed to the treasury
+    pub claimed_at: u64,
+}
+
+/// Receipt returned from a claim, mirroring the emitted `ClaimEvent`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimReceipt {
+    pub grant_id: u64,
+    pub amount: i128,        // Released by this claim
+    pub total_claimed: i128, // Cumulative claimed after this claim
+    pub remaining: i128,     // Still to be claimed over the rest of the schedule
+    pub fee: i128,           // Part of `amount` routed to the treasury
     pub claimed_at: u64,
 }
 
+/// Why a claim on a schedule would or would not go through right now
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimEligibility {
+    pub eligible: bool, // A claim by the beneficiary would succeed (cosign aside)
+    pub in_cliff: bool, // Not started yet or still inside the cliff
+    pub paused: bool,   // Contract-wide pause in progress (claims still run)
+    pub blocked: bool,  // Beneficiary is blocked by governance
+    pub cooldown_active: bool, // Claimed too recently (cooldown or same ledger)
+    pub below_minimum: bool, // Something is claimable but less than `min_claim`
+    pub nothing_claimable: bool, // Nothing vested and unclaimed within the period cap
+}
+
 /// Alias event for vesting claim (VestingClaimed for indexer)
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -51,6 +163,24 @@ pub struct VestingClaimed {
     pub claimed_at: u64,
 }
 
+/// One summary event standing in for a batch's per-schedule events
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchEvent {
+    pub operation: Symbol,
+    pub count: u32,
+    pub total_amount: i128,
+}
+
+/// Context published alongside an error (`required` vs `available` base units)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorDiagnostic {
+    pub grant_id: u64, // Schedule involved, 0 when not schedule-specific
+    pub required: i128,
+    pub available: i128,
+}
+
 /// Credential issued event (alias for grant event)
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -75,6 +205,277 @@ pub struct RevokeEvent {
     pub revoked_by: Address,
 }
 
+/// Rounding applied to partially vested amounts
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RoundingMode {
+    Floor = 0,
+    Ceil = 1,
+}
+
+/// Lifecycle stage of a schedule at the current vesting clock
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ScheduleStatus {
+    Pending = 0,     // Not started (future start, acceptance or milestone outstanding)
+    Cliff = 1,       // Started but still inside the cliff
+    Vesting = 2,     // Accruing linearly
+    FullyVested = 3, // Everything vested, some still unclaimed
+    Claimed = 4,
+    Revoked = 5,
+}
+
+/// Governance-tunable parameters, written atomically via `set_config`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobalConfig {
+    pub min_claim: i128, // Smallest amount (base units) a claim may release (0 = no minimum)
+    pub max_schedules_per_user: u32, // Live schedules allowed per beneficiary (0 = unlimited)
+    pub default_cooldown: u64, // Seconds between claims by the same beneficiary
+    pub rounding_mode: RoundingMode,
+    pub commitment_cap: i128, // Cap on outstanding unclaimed base units (0 = unlimited)
+    pub default_cliff_bps: u32, // Cliff applied when a grant omits one, in bps of duration
+    pub pause_halts_vesting: bool, // Exclude contract-wide pause time from vesting progress
+    pub auto_finalize: bool,  // Delete schedules in the claim that fully claims them
+    pub transfer_veto_window: u64, // Seconds a transfer waits for a governance veto (0 = immediate)
+    pub require_collateral: bool, // Grants must be covered by the contract's token balance
+    pub governance_timelock: u64, // Seconds between proposing and accepting new governance
+    pub claim_fee_bps: u32,   // Share of each claim sent to the treasury
+    pub grant_cancellation_window: u64, // Seconds after a grant during which `cancel_grant` works
+    pub claim_on_transfer: bool, // Pay out vested tokens to the old beneficiary before a transfer
+    pub summarize_batches: bool, // Batch claims emit one `BatchEvent` instead of per-schedule events
+}
+
+/// Numeric subset of `GlobalConfig`, flattened for monitoring diffs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Parameters {
+    pub min_claim: i128,
+    pub max_schedules_per_user: u32,
+    pub commitment_cap: i128,
+    pub default_cooldown: u64,
+    pub transfer_veto_window: u64,
+    pub governance_timelock: u64,
+    pub default_cliff_bps: u32,
+    pub claim_fee_bps: u32,
+    pub grant_cancellation_window: u64,
+    pub rounding_mode: RoundingMode,
+}
+
+/// Storage keys for per-address vesting data
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum AcademyDataKey {
+    /// Default claim destination chosen by a beneficiary (Address → Address)
+    ClaimDestination(Address),
+    /// Timestamp of a beneficiary's most recent claim (Address → u64)
+    LastClaim(Address),
+    /// Grant IDs issued to a beneficiary (Address → Vec<u64>)
+    UserScheduleIds(Address),
+    /// Governance block on claims by a beneficiary (Address → bool)
+    Blocked(Address),
+    /// Admin address (v2 layout, instance storage)
+    Admin,
+    /// Reward token address (v2 layout, instance storage)
+    RewardToken,
+    /// Governance address (v2 layout, instance storage)
+    Governance,
+    /// Last issued grant ID (v2 layout, instance storage)
+    GrantCounter,
+    /// Storage layout version (absent on v1 deployments)
+    StorageVersion,
+    /// Every storage version migrated to, with when (→ Vec<(u32, u64)>)
+    VersionHistory,
+    /// Destinations sharing a schedule's claims, in bps (grant ID → Vec<(Address, u32)>)
+    ClaimSplit(u64),
+    /// Start of a capped schedule's current claim period and what it has claimed in it
+    /// (grant ID → (u64, i128))
+    PeriodClaims(u64),
+    /// Treasury receiving reclaimed tokens (defaults to governance)
+    Treasury,
+    /// Earliest start_time a new grant may use
+    MinStart,
+    /// Shortest duration a new grant may use, in seconds
+    MinDuration,
+    /// Set while schedule transfers are paused; grants and claims are unaffected
+    TransfersPaused,
+    /// Signer set replacing the single admin, with its approval threshold (→ (Vec<Address>, u32))
+    AdminSigners,
+    /// Co-signers approving a signer's next call of an admin action ((Address, Symbol) → Vec<Address>)
+    AdminApprovals(Address, Symbol),
+    /// Number of distinct addresses ever given a schedule
+    BeneficiaryCount,
+    /// Marks an address as already counted in `BeneficiaryCount` (Address → bool)
+    SeenBeneficiary(Address),
+    /// Optional SEP-41 token minted to beneficiaries alongside each claim
+    ReceiptToken,
+    /// Decimals of the reward token, captured at init
+    TokenDecimals,
+    /// Staking contracts approved for `claim_and_stake` (Address → bool)
+    StakingContract(Address),
+    /// Seconds a milestone cuts from a schedule's end, and whether it was met
+    /// (grant ID → (u64, bool))
+    MilestoneAcceleration(u64),
+    /// Oracle allowed to confirm a milestone-gated schedule (grant ID → Address)
+    MilestoneOracle(u64),
+    /// Grant created under a client-supplied key, with when it was recorded (→ (u64, u64))
+    IdempotencyKey(BytesN<32>),
+    /// Number of schedules neither fully claimed nor revoked
+    ActiveCount,
+    /// Ledger (sequence, timestamp) of a schedule's most recent claim (grant ID → (u32, u64))
+    LastClaimLedger(u64),
+    /// Ordered hook contracts notified of lifecycle events (→ Vec<Address>)
+    Hooks,
+    /// Transfer awaiting its veto window (grant ID → PendingTransfer)
+    PendingTransfer(u64),
+    /// Governance handover awaiting acceptance (→ PendingGovernance)
+    PendingGovernance,
+    /// Ring buffer of the most recent privileged actions (→ Vec<AdminLogEntry>)
+    AdminLog,
+    /// Most recent claims on a schedule, oldest first (grant ID → Vec<ClaimRecord>)
+    ClaimHistory(u64),
+    /// Destinations a beneficiary's claims may be sent to besides themselves; empty or
+    /// missing means unrestricted (Address → Vec<Address>)
+    AllowedDestinations(Address),
+    /// Status a schedule had when `tick` last looked at it (grant ID → ScheduleStatus)
+    LastStatus(u64),
+    /// Tokens paid in so far for an incrementally funded schedule (grant ID → i128)
+    FundedAmount(u64),
+    /// Every token a grant has been made in (→ Vec<Address>)
+    UsedTokens,
+    /// IDs of schedules neither fully claimed nor revoked, in grant order (→ Vec<u64>)
+    ActiveIds,
+    /// Running (committed, claimed, outstanding) base units over stored schedules
+    Totals,
+    /// Base units revoke, reduce and cancel have returned to the treasury (→ i128)
+    TotalReclaimed,
+    /// Contract a beneficiary wants told about their own claims (Address → Address)
+    PersonalNotifier(Address),
+    /// Amount a schedule had vested when it was revoked after starting (grant ID → i128)
+    VestedAtRevoke(u64),
+    /// Named grant template (Symbol → GrantTemplate)
+    Template(Symbol),
+    /// Names of every stored template, in creation order (→ Vec<Symbol>)
+    TemplateNames,
+    /// Pinned vesting clock, honoured only in test builds (→ u64)
+    MockTime,
+    /// Marks a caller-chosen grant ID as used, even after its schedule is deleted (→ bool)
+    IssuedId(u64),
+}
+
+/// Read-only preview of what `migrate_storage` would move
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationReport {
+    pub current_version: u32,
+    pub target_version: u32,
+    pub legacy_admin: bool,
+    pub legacy_token: bool,
+    pub legacy_governance: bool,
+    pub legacy_counter: bool,
+    pub keys_to_migrate: u32,
+    pub legacy_schedules: u32,
+}
+
+/// Diagnostic view of a beneficiary's schedule index against stored schedules
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UserKeysReport {
+    pub indexed: Vec<u64>, // Every grant ID in the beneficiary's index
+    pub missing: Vec<u64>, // Indexed IDs with no backing schedule entry
+}
+
+/// Contract-wide cross-check of the schedule indexes against stored schedules
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexAuditReport {
+    pub scanned: u64,             // Stored schedules checked
+    pub active_orphaned: u32,     // Active-index IDs that are missing, revoked or claimed
+    pub active_missing: u32,      // Live schedules absent from the active index
+    pub active_count_drift: bool, // `ActiveCount` disagrees with the active index length
+    pub user_orphaned: u32,       // User-index IDs missing or owned by someone else
+    pub user_missing: u32,        // Schedules absent from their beneficiary's index
+}
+
+/// What an address may do here, for frontends choosing which controls to show
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Roles {
+    pub is_admin: bool,
+    pub is_governance: bool,
+    pub is_granter: bool, // May create grants (currently the admin)
+    pub has_schedules: bool,
+}
+
+/// Transfer event for off-chain indexing
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TransferEvent {
+    pub grant_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub transferred_at: u64,
+}
+
+/// Transfer queued behind the governance veto window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingTransfer {
+    pub new_beneficiary: Address,
+    pub executable_at: u64,
+}
+
+/// One claim recorded in a schedule's history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimRecord {
+    pub amount: i128,
+    pub claimed_at: u64,
+    pub ledger: u32,
+}
+
+/// Everything an indexer needs to rebuild a schedule's lifecycle in one read
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScheduleReplay {
+    pub grant_id: u64,
+    pub schedule: VestingSchedule, // Grant parameters plus current claimed/revoked state
+    pub status: ScheduleStatus,
+    pub claims: Vec<ClaimRecord>, // Oldest first; only the last `MAX_CLAIM_HISTORY` are kept
+    pub vested_at_revoke: i128,   // Vested amount frozen by a revoke (0 if none retained)
+    pub vested_now: i128,
+}
+
+/// One privileged action recorded in the admin log
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminLogEntry {
+    pub actor: Address,
+    pub action: Symbol, // Entry point name, e.g. `set_config`
+    pub at: u64,
+}
+
+/// Governance handover proposed by the current governance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingGovernance {
+    pub new_governance: Address,
+    pub accept_after: u64,
+}
+
+/// Reduce event emitted when governance claws down an unvested grant
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReduceEvent {
+    pub grant_id: u64,
+    pub old_amount: i128,
+    pub new_amount: i128,
+    pub reclaimed: i128,
+    pub reduced_at: u64,
+}
+
 /// Vesting error codes
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -89,6 +490,46 @@ pub enum VestingError {
     InvalidTimelock = 4008,
     NotEnoughTimeForRevoke = 4009,
     BatchTooLarge = 4010,
+    BelowMinimumClaim = 4011,
+    TooManySchedules = 4012,
+    ClaimCooldownActive = 4013,
+    CommitmentCapExceeded = 4014,
+    InvalidConfig = 4015,
+    BeneficiaryBlocked = 4016,
+    VestingStarted = 4017,
+    GrantNotPending = 4018,
+    NotTransferable = 4019,
+    StartTooEarly = 4020,
+    InvariantViolation = 4021,
+    CosignRequired = 4022,
+    StakingNotAllowed = 4023,
+    StakeFailed = 4024,
+    ClaimTooSoon = 4025,
+    TransferPending = 4026,
+    NoPendingTransfer = 4027,
+    VetoWindowActive = 4028,
+    AdminNotSet = 4029,
+    TokenNotSet = 4030,
+    GovernanceNotSet = 4031,
+    UnderCollateralized = 4032,
+    NoPendingGovernance = 4033,
+    GovernanceTimelockActive = 4034,
+    TemplateNotFound = 4035,
+    CancellationWindowClosed = 4036,
+    DurationTooShort = 4037,
+    InvalidSplit = 4038,
+    PeriodCapExceeded = 4039,
+    DataTooLarge = 4040,
+    InvalidBeneficiary = 4041,
+    MilestoneAlreadyMet = 4042,
+    TransfersPaused = 4043,
+    ScheduleIdTaken = 4044,
+    InvalidAmount = 4045,
+    AdminThresholdNotMet = 4046,
+    DestinationNotAllowed = 4047,
+    InsufficientFunded = 4048,
+    DuplicateGrantId = 4049,
+    InsufficientContractBalance = 4050,
 }
 
 impl From<VestingError> for soroban_sdk::Error {
@@ -109,6 +550,14 @@ impl From<soroban_sdk::Error> for VestingError {
     }
 }
 
+/// Interface implemented by contracts registered through `add_hook`
+#[contractclient(name = "VestingHookClient")]
+pub trait VestingHook {
+    fn on_grant(env: Env, grant_id: u64, beneficiary: Address, amount: i128);
+    fn on_claim(env: Env, grant_id: u64, beneficiary: Address, amount: i128);
+    fn on_revoke(env: Env, grant_id: u64, beneficiary: Address, amount: i128);
+}
+
 #[contract]
 pub struct AcademyVestingContract;
 
@@ -125,372 +574,4883 @@ fn persist_schedules(env: &Env, schedules: &soroban_sdk::Map<u64, VestingSchedul
         .set(&symbol_short!("sched"), schedules);
 }
 
-fn claimable_amount(
-    env: &Env,
-    grant_id: u64,
-    beneficiary: &Address,
-    schedule: &VestingSchedule,
-) -> Result<i128, VestingError> {
-    if schedule.beneficiary != *beneficiary {
-        return Err(VestingError::Unauthorized);
+/// Read a core value from its v2 instance key, falling back to the v1 persistent symbol key
+fn read_core<V: TryFromVal<Env, Val>>(env: &Env, key: AcademyDataKey, legacy: Symbol) -> Option<V> {
+    env.storage()
+        .instance()
+        .get(&key)
+        .or_else(|| env.storage().persistent().get(&legacy))
+}
+
+fn read_admin(env: &Env) -> Option<Address> {
+    read_core(env, AcademyDataKey::Admin, symbol_short!("admin"))
+}
+
+fn admin_signers(env: &Env) -> Option<(Vec<Address>, u32)> {
+    env.storage().instance().get(&AcademyDataKey::AdminSigners)
+}
+
+fn is_admin_address(env: &Env, who: &Address) -> bool {
+    match admin_signers(env) {
+        Some((signers, _)) => signers.contains(who),
+        None => read_admin(env).as_ref() == Some(who),
     }
+}
 
-    if schedule.claimed {
-        return Err(VestingError::AlreadyClaimed);
+/// Check `admin` may make a privileged call; the caller has already required its auth.
+///
+/// With a signer set configured, `admin` must be a signer whose pending co-signer
+/// approvals for `action` (its admin-log name) bring it to the threshold. Each approving
+/// co-signer must also authorize this exact invocation, so an approval cannot be spent on
+/// other arguments either. Those approvals are used up by the call.
+fn require_admin(env: &Env, admin: &Address, action: &str) -> Result<(), VestingError> {
+    let Some((signers, threshold)) = admin_signers(env) else {
+        let stored_admin = read_admin(env).ok_or(VestingError::AdminNotSet)?;
+        if *admin != stored_admin {
+            return Err(VestingError::Unauthorized);
+        }
+        return Ok(());
+    };
+    if !signers.contains(admin) {
+        return Err(VestingError::Unauthorized);
     }
 
-    if schedule.revoked {
-        return Err(VestingError::Revoked);
+    let approvals_key = AcademyDataKey::AdminApprovals(admin.clone(), Symbol::new(env, action));
+    let approvals: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&approvals_key)
+        .unwrap_or_else(|| Vec::new(env));
+    // Approvals from signers removed since they were given no longer count
+    let mut co_signers = Vec::new(env);
+    for signer in approvals.iter() {
+        if signers.contains(&signer) {
+            co_signers.push_back(signer);
+        }
     }
+    if co_signers.len() + 1 < threshold {
+        return Err(VestingError::AdminThresholdNotMet);
+    }
+    for co_signer in co_signers.iter() {
+        co_signer.require_auth();
+    }
+    env.storage().persistent().remove(&approvals_key);
 
-    let current_time = env.ledger().timestamp();
-    let vested_amount = AcademyVestingContract::calculate_vested_amount(schedule, current_time)?;
+    Ok(())
+}
 
-    if vested_amount == 0 {
-        return Err(VestingError::NotVested);
-    }
+fn read_token(env: &Env) -> Option<Address> {
+    read_core(env, AcademyDataKey::RewardToken, symbol_short!("token"))
+}
 
-    let _ = grant_id;
-    Ok(vested_amount)
+fn read_governance(env: &Env) -> Option<Address> {
+    read_core(env, AcademyDataKey::Governance, symbol_short!("gov"))
 }
 
-#[contractimpl]
-impl AcademyVestingContract {
-    /// Initialize the vesting contract with admin and governance roles
-    pub fn init(
-        env: Env,
-        admin: Address,
-        reward_token: Address,
-        governance: Address,
-        cb_config: CircuitBreakerConfig,
-    ) -> Result<(), VestingError> {
-        // Check if already initialized
-        let init_key = symbol_short!("init");
-        if env.storage().persistent().has(&init_key) {
-            return Err(VestingError::Unauthorized);
-        }
+fn read_grant_counter(env: &Env) -> u64 {
+    read_core(env, AcademyDataKey::GrantCounter, symbol_short!("cnt")).unwrap_or(0)
+}
 
-        // Set initialization flag
-        env.storage().persistent().set(&init_key, &true);
+/// The ID after the counter, stepping over any taken by `grant_with_id`
+fn next_grant_id(env: &Env, schedules: &soroban_sdk::Map<u64, VestingSchedule>) -> u64 {
+    let mut id = read_grant_counter(env) + 1;
+    while id_issued(env, schedules, id) {
+        id += 1;
+    }
+    id
+}
 
-        // Store admin
-        let admin_key = symbol_short!("admin");
-        env.storage().persistent().set(&admin_key, &admin);
+/// Whether `id` has ever named a schedule: every ID up to the counter has, and caller-chosen
+/// IDs above it stay marked after their schedule is deleted so per-grant keys left behind
+/// are never inherited by a new grant
+fn id_issued(env: &Env, schedules: &soroban_sdk::Map<u64, VestingSchedule>, id: u64) -> bool {
+    id <= read_grant_counter(env)
+        || schedules.contains_key(id)
+        || env
+            .storage()
+            .persistent()
+            .has(&AcademyDataKey::IssuedId(id))
+}
 
-        // Store reward token
-        let token_key = symbol_short!("token");
-        env.storage().persistent().set(&token_key, &reward_token);
+fn read_treasury(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::Treasury)
+        .or_else(|| read_governance(env))
+}
 
-        // Store governance address
-        let gov_key = symbol_short!("gov");
-        env.storage().persistent().set(&gov_key, &governance);
+fn read_min_start(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::MinStart)
+        .unwrap_or(0)
+}
 
-        // Store roles for shared GovernanceManager compatibility
-        let mut roles = soroban_sdk::Map::new(&env);
-        roles.set(admin.clone(), shared::governance::GovernanceRole::Admin);
-        env.storage()
-            .persistent()
-            .set(&symbol_short!("roles"), &roles);
+fn read_min_duration(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::MinDuration)
+        .unwrap_or(0)
+}
 
-        // Initialize grant counter
-        let counter_key = symbol_short!("cnt");
-        env.storage().persistent().set(&counter_key, &0u64);
+fn token_decimals(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::TokenDecimals)
+        .unwrap_or(DEFAULT_TOKEN_DECIMALS)
+}
 
-        // Initialize circuit breaker
-        CircuitBreaker::init(&env, cb_config);
+/// `(1 + apy / 365)^days` in `ACCRUAL_SCALE` fixed point, or `None` past `MAX_ACCRUAL_GROWTH`
+fn accrual_growth(apy_bps: u32, days: u64) -> Option<u128> {
+    let mut base =
+        ACCRUAL_SCALE + ACCRUAL_SCALE * apy_bps as u128 / (BPS_DENOMINATOR as u128 * 365);
+    let mut growth = ACCRUAL_SCALE;
+    let mut exponent = days;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            growth = growth.checked_mul(base)? / ACCRUAL_SCALE;
+            if growth > MAX_ACCRUAL_GROWTH {
+                return None;
+            }
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base.checked_mul(base)? / ACCRUAL_SCALE;
+            if base > MAX_ACCRUAL_GROWTH {
+                return None;
+            }
+        }
+    }
+    Some(growth)
+}
 
-        Ok(())
+/// Share of the curve unlocked after `elapsed` of `span` seconds, in `ACCRUAL_SCALE`
+fn accrued_fraction(apy_bps: u32, elapsed: u64, span: u64) -> Option<u128> {
+    let total = accrual_growth(apy_bps, span.div_ceil(SECONDS_PER_DAY))?;
+    let so_far = accrual_growth(apy_bps, elapsed / SECONDS_PER_DAY)?;
+    if total <= ACCRUAL_SCALE {
+        return None;
     }
+    Some((so_far - ACCRUAL_SCALE) * ACCRUAL_SCALE / (total - ACCRUAL_SCALE))
+}
 
-    /// Grant a vesting schedule to a beneficiary
-    pub fn grant_vesting(
-        env: Env,
-        admin: Address,
-        beneficiary: Address,
-        amount: i128,
-        start_time: u64,
-        cliff: u64,
-        duration: u64,
-    ) -> Result<u64, VestingError> {
-        admin.require_auth();
+/// Base units making up one whole reward token
+fn base_units_per_token(env: &Env) -> Result<i128, VestingError> {
+    10i128
+        .checked_pow(token_decimals(env))
+        .ok_or(VestingError::InvalidConfig)
+}
 
-        // Check pause state via CircuitBreaker
-        CircuitBreaker::require_not_paused(&env, symbol_short!("grant"));
+fn storage_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::StorageVersion)
+        .unwrap_or(1)
+}
 
-        // Verify caller is admin
-        let admin_key = symbol_short!("admin");
-        let stored_admin: Address = env
-            .storage()
-            .persistent()
-            .get(&admin_key)
-            .ok_or(VestingError::Unauthorized)?;
+/// Legacy v1 keys paired with their v2 destinations, in migration order
+fn legacy_core_keys() -> [(Symbol, AcademyDataKey); 4] {
+    [
+        (symbol_short!("admin"), AcademyDataKey::Admin),
+        (symbol_short!("token"), AcademyDataKey::RewardToken),
+        (symbol_short!("gov"), AcademyDataKey::Governance),
+        (symbol_short!("cnt"), AcademyDataKey::GrantCounter),
+    ]
+}
 
-        if admin != stored_admin {
-            return Err(VestingError::Unauthorized);
-        }
+/// Whether `value` is a field map with exactly the given field names.
+///
+/// Decoding a `#[contracttype]` struct from a map with a different field set traps in the host
+/// rather than failing softly, so the raw keys are checked before any such decode.
+fn has_exact_fields(env: &Env, value: &Val, names: &[&str]) -> bool {
+    let Ok(fields) = soroban_sdk::Map::<Symbol, Val>::try_from_val(env, value) else {
+        return false;
+    };
+    fields.len() == names.len() as u32
+        && names
+            .iter()
+            .all(|field| fields.contains_key(Symbol::new(env, field)))
+}
 
-        // Validate schedule
-        if amount <= 0 {
-            return Err(VestingError::InvalidSchedule);
+/// Whether `value` has exactly the v1 schedule fields
+fn is_legacy_schedule(env: &Env, value: &Val) -> bool {
+    has_exact_fields(
+        env,
+        value,
+        &[
+            "amount",
+            "beneficiary",
+            "claimed",
+            "cliff",
+            "duration",
+            "revoke_time",
+            "revoked",
+            "start_time",
+        ],
+    )
+}
+
+/// Whether `value` has exactly the current `VestingSchedule` fields
+fn is_current_schedule(env: &Env, value: &Val) -> bool {
+    has_exact_fields(
+        env,
+        value,
+        &[
+            "beneficiary",
+            "amount",
+            "start_time",
+            "cliff",
+            "duration",
+            "claimed",
+            "claimed_amount",
+            "revoked",
+            "revoke_time",
+            "pause_offset",
+            "pending_acceptance",
+            "transferable",
+            "requires_cosign",
+            "granted_by",
+            "milestone_met",
+            "tail_bps",
+            "created_at",
+            "max_claim_per_period",
+            "period_seconds",
+            "data",
+            "curve",
+            "memo",
+        ],
+    )
+}
+
+/// Stored schedules still in the v1 layout, which the current `VestingSchedule` cannot decode
+fn legacy_schedules(env: &Env) -> Vec<(u64, LegacyVestingSchedule)> {
+    let raw: soroban_sdk::Map<u64, Val> = env
+        .storage()
+        .persistent()
+        .get(&symbol_short!("sched"))
+        .unwrap_or_else(|| soroban_sdk::Map::new(env));
+
+    let mut legacy = Vec::new(env);
+    for (grant_id, value) in raw.iter() {
+        if !is_legacy_schedule(env, &value) {
+            continue;
         }
-        if cliff > duration {
-            return Err(VestingError::InvalidSchedule);
+        if let Ok(schedule) = LegacyVestingSchedule::try_from_val(env, &value) {
+            legacy.push_back((grant_id, schedule));
         }
+    }
+    legacy
+}
 
-        // Get next grant ID
-        let counter_key = symbol_short!("cnt");
-        let grant_id: u64 = env.storage().persistent().get(&counter_key).unwrap_or(0u64);
+/// A v1 schedule in the current layout, with every later field at its neutral default.
+///
+/// v1 recorded neither creation time nor a claimed total: `created_at` is 0, and a claimed
+/// schedule counts as fully paid out since v1 closed it with its only claim.
+fn upgrade_legacy_schedule(
+    env: &Env,
+    legacy: LegacyVestingSchedule,
+    granted_by: &Address,
+) -> VestingSchedule {
+    VestingSchedule {
+        claimed_amount: if legacy.claimed { legacy.amount } else { 0 },
+        beneficiary: legacy.beneficiary,
+        amount: legacy.amount,
+        start_time: legacy.start_time,
+        cliff: legacy.cliff,
+        duration: legacy.duration,
+        claimed: legacy.claimed,
+        revoked: legacy.revoked,
+        revoke_time: legacy.revoke_time,
+        pause_offset: paused_seconds(env),
+        pending_acceptance: false,
+        transferable: true,
+        requires_cosign: false,
+        granted_by: granted_by.clone(),
+        milestone_met: true,
+        tail_bps: 0,
+        created_at: 0,
+        max_claim_per_period: 0,
+        period_seconds: 0,
+        data: Bytes::new(env),
+        curve: VestingCurve::Linear,
+        memo: Symbol::new(env, ""),
+    }
+}
 
-        let next_id = grant_id + 1;
+/// Rewrite v1 schedules in the current layout and register them in the indexes and totals
+/// v1 never kept. Entries that decode as neither layout are left for `try_get_schedule`.
+fn migrate_legacy_schedules(env: &Env, granted_by: &Address) -> u32 {
+    let legacy = legacy_schedules(env);
+    if legacy.is_empty() {
+        return 0;
+    }
 
-        // Create vesting schedule
-        let schedule = VestingSchedule {
-            beneficiary: beneficiary.clone(),
-            amount,
-            start_time,
-            cliff,
-            duration,
-            claimed: false,
-            revoked: false,
-            revoke_time: 0,
-        };
+    let mut raw: soroban_sdk::Map<u64, Val> = env
+        .storage()
+        .persistent()
+        .get(&symbol_short!("sched"))
+        .unwrap_or_else(|| soroban_sdk::Map::new(env));
+    for (grant_id, old) in legacy.iter() {
+        let schedule = upgrade_legacy_schedule(env, old, granted_by);
+        index_user_schedule(env, &schedule.beneficiary, grant_id);
+        if !schedule.claimed && !schedule.revoked {
+            update_active(env, grant_id, true);
+        }
+        adjust_totals(
+            env,
+            schedule.amount,
+            schedule.claimed_amount,
+            unclaimed_obligation(env, grant_id, &schedule),
+        );
+        raw.set(grant_id, schedule.into_val(env));
+    }
+    env.storage()
+        .persistent()
+        .set(&symbol_short!("sched"), &raw);
 
-        // Store schedule
-        let schedules_key = symbol_short!("sched");
-        let mut schedules: soroban_sdk::Map<u64, VestingSchedule> = env
-            .storage()
-            .persistent()
-            .get(&schedules_key)
-            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+    legacy.len()
+}
 
-        schedules.set(next_id, schedule);
-        env.storage().persistent().set(&schedules_key, &schedules);
+fn default_config() -> GlobalConfig {
+    GlobalConfig {
+        min_claim: 0,
+        max_schedules_per_user: 0,
+        default_cooldown: 0,
+        rounding_mode: RoundingMode::Floor,
+        commitment_cap: 0,
+        default_cliff_bps: 0,
+        pause_halts_vesting: false,
+        auto_finalize: false,
+        transfer_veto_window: 0,
+        require_collateral: false,
+        governance_timelock: 0,
+        claim_fee_bps: 0,
+        grant_cancellation_window: 0,
+        claim_on_transfer: false,
+        summarize_batches: false,
+    }
+}
 
-        // Update counter
-        env.storage().persistent().set(&counter_key, &next_id);
+/// Total seconds the contract has spent paused, including any pause still in progress
+fn paused_seconds(env: &Env) -> u64 {
+    let completed: u64 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("paused_s"))
+        .unwrap_or(0);
+    let ongoing = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&symbol_short!("paused_at"))
+        .map(|paused_at| env.ledger().timestamp().saturating_sub(paused_at))
+        .unwrap_or(0);
 
-        let current_timestamp = env.ledger().timestamp();
+    completed + ongoing
+}
+
+/// Timestamp the vesting math runs on: the ledger's, unless a test build pinned one
+fn clock_now(env: &Env) -> u64 {
+    #[cfg(any(test, feature = "testutils"))]
+    if let Some(mock) = env.storage().instance().get(&AcademyDataKey::MockTime) {
+        return mock;
+    }
+
+    env.ledger().timestamp()
+}
+
+/// Timestamp used for vesting math, discounting pause time when configured
+fn vesting_clock(env: &Env, config: &GlobalConfig, schedule: &VestingSchedule) -> u64 {
+    let now = clock_now(env);
+    if !config.pause_halts_vesting {
+        return now;
+    }
+
+    let paused_since_grant = paused_seconds(env).saturating_sub(schedule.pause_offset);
+    now.saturating_sub(paused_since_grant)
+}
+
+fn load_config(env: &Env) -> GlobalConfig {
+    env.storage()
+        .persistent()
+        .get(&symbol_short!("config"))
+        .unwrap_or_else(default_config)
+}
+
+fn require_governance(env: &Env, governance: &Address) -> Result<(), VestingError> {
+    governance.require_auth();
+
+    let stored_governance = read_governance(env).ok_or(VestingError::GovernanceNotSet)?;
+
+    if *governance != stored_governance {
+        return Err(VestingError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+fn user_schedule_ids(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::UserScheduleIds(user.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn beneficiary_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::BeneficiaryCount)
+        .unwrap_or(0)
+}
+
+fn active_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::ActiveCount)
+        .unwrap_or(0)
+}
+
+fn active_ids(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::ActiveIds)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Track a schedule entering (`true`) or leaving (`false`) the active set
+fn update_active(env: &Env, grant_id: u64, entering: bool) {
+    let mut ids = active_ids(env);
+    let count = if entering {
+        ids.push_back(grant_id);
+        active_count(env) + 1
+    } else {
+        if let Some(index) = ids.first_index_of(grant_id) {
+            ids.remove(index);
+        }
+        active_count(env).saturating_sub(1)
+    };
+    env.storage()
+        .persistent()
+        .set(&AcademyDataKey::ActiveIds, &ids);
+    env.storage()
+        .instance()
+        .set(&AcademyDataKey::ActiveCount, &count);
+}
+
+fn admin_log(env: &Env) -> Vec<AdminLogEntry> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::AdminLog)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Append to the admin log, evicting the oldest entry once `MAX_ADMIN_LOG` is reached
+fn log_admin_action(env: &Env, actor: &Address, action: &str) {
+    let mut log = admin_log(env);
+    if log.len() >= MAX_ADMIN_LOG {
+        log.pop_front();
+    }
+    log.push_back(AdminLogEntry {
+        actor: actor.clone(),
+        action: Symbol::new(env, action),
+        at: env.ledger().timestamp(),
+    });
+    env.storage()
+        .persistent()
+        .set(&AcademyDataKey::AdminLog, &log);
+}
+
+fn totals(env: &Env) -> (i128, i128, i128) {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::Totals)
+        .unwrap_or((0, 0, 0))
+}
+
+/// Shift the running totals by the given committed, claimed and outstanding deltas
+fn adjust_totals(env: &Env, committed: i128, claimed: i128, outstanding: i128) {
+    let (total_committed, total_claimed, total_outstanding) = totals(env);
+    env.storage().instance().set(
+        &AcademyDataKey::Totals,
+        &(
+            total_committed + committed,
+            total_claimed + claimed,
+            total_outstanding + outstanding,
+        ),
+    );
+}
+
+fn total_reclaimed(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::TotalReclaimed)
+        .unwrap_or(0)
+}
+
+fn add_reclaimed(env: &Env, amount: i128) {
+    env.storage().instance().set(
+        &AcademyDataKey::TotalReclaimed,
+        &(total_reclaimed(env) + amount),
+    );
+}
+
+fn used_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::UsedTokens)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Add a grant's token to the `UsedTokens` set
+fn record_used_token(env: &Env, token: Address) {
+    let mut tokens = used_tokens(env);
+    if !tokens.contains(&token) {
+        tokens.push_back(token);
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::UsedTokens, &tokens);
+    }
+}
+
+fn index_user_schedule(env: &Env, user: &Address, grant_id: u64) {
+    let seen_key = AcademyDataKey::SeenBeneficiary(user.clone());
+    if !env.storage().persistent().has(&seen_key) {
+        env.storage().persistent().set(&seen_key, &true);
+
+        let count = beneficiary_count(env) + 1;
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::BeneficiaryCount, &count);
+        env.events()
+            .publish((symbol_short!("new_benef"),), (user.clone(), count));
+    }
+
+    let mut ids = user_schedule_ids(env, user);
+    ids.push_back(grant_id);
+    env.storage()
+        .persistent()
+        .set(&AcademyDataKey::UserScheduleIds(user.clone()), &ids);
+}
+
+fn transfers_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::TransfersPaused)
+        .unwrap_or(false)
+}
+
+fn is_blocked(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::Blocked(user.clone()))
+        .unwrap_or(false)
+}
+
+fn claimable_amount(
+    env: &Env,
+    grant_id: u64,
+    beneficiary: &Address,
+    schedule: &VestingSchedule,
+) -> Result<i128, VestingError> {
+    if schedule.beneficiary != *beneficiary {
+        return Err(VestingError::Unauthorized);
+    }
+
+    if schedule.claimed {
+        return Err(VestingError::AlreadyClaimed);
+    }
+
+    // A revoked schedule only releases what had vested before the revoke
+    if schedule.revoked && unclaimed_obligation(env, grant_id, schedule) == 0 {
+        return Err(VestingError::Revoked);
+    }
+
+    if is_blocked(env, beneficiary) {
+        return Err(VestingError::BeneficiaryBlocked);
+    }
+
+    // Schedules are locked while a transfer waits out its veto window
+    if has_pending_transfer(env, grant_id) {
+        return Err(VestingError::TransferPending);
+    }
+
+    // One claim per schedule per ledger keeps claim history from being spammed
+    let last_ledger: Option<(u32, u64)> = env
+        .storage()
+        .persistent()
+        .get(&AcademyDataKey::LastClaimLedger(grant_id));
+    if last_ledger == Some(current_ledger(env)) {
+        return Err(VestingError::ClaimTooSoon);
+    }
+
+    let config = load_config(env);
+    let current_time = env.ledger().timestamp();
+    let claimable = unclaimed_vested(env, &config, grant_id, schedule)?;
+    if claimable <= 0 {
+        return Err(VestingError::NotVested);
+    }
+
+    // Incrementally funded schedules release no more than has been paid in
+    let claimable = match funded_amount(env, grant_id) {
+        Some(funded) if funded <= schedule.claimed_amount => {
+            return Err(VestingError::InsufficientFunded);
+        }
+        Some(funded) => claimable.min(funded - schedule.claimed_amount),
+        None => claimable,
+    };
+
+    // Period-capped schedules release at most what is left of the current period's cap
+    let claimable = if schedule.period_seconds > 0 {
+        let allowance = period_allowance(env, grant_id, schedule);
+        if allowance == 0 {
+            return Err(VestingError::PeriodCapExceeded);
+        }
+        claimable.min(allowance)
+    } else {
+        claimable
+    };
+
+    if claimable < config.min_claim && !is_final_sweep(env, grant_id, schedule, claimable) {
+        return Err(VestingError::BelowMinimumClaim);
+    }
+
+    if config.default_cooldown > 0 {
+        let last_claim: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&AcademyDataKey::LastClaim(beneficiary.clone()));
+        if let Some(last_claim) = last_claim {
+            if current_time < last_claim + config.default_cooldown {
+                return Err(VestingError::ClaimCooldownActive);
+            }
+        }
+    }
+
+    Ok(claimable)
+}
+
+/// Whether `claimable` releases everything the schedule still owes, with nothing further to vest.
+///
+/// Such a claim is exempt from `min_claim`, so neither rounding dust nor a whole grant smaller
+/// than the minimum is ever stranded.
+fn is_final_sweep(env: &Env, grant_id: u64, schedule: &VestingSchedule, claimable: i128) -> bool {
+    claimable == unclaimed_obligation(env, grant_id, schedule)
+}
+
+/// Vested tokens not yet claimed (for a revoked schedule, what it retained)
+fn unclaimed_vested(
+    env: &Env,
+    config: &GlobalConfig,
+    grant_id: u64,
+    schedule: &VestingSchedule,
+) -> Result<i128, VestingError> {
+    if schedule.revoked {
+        return Ok(unclaimed_obligation(env, grant_id, schedule));
+    }
+
+    Ok(AcademyVestingContract::calculate_vested_amount(
+        schedule,
+        vesting_clock(env, config, schedule),
+        config.rounding_mode,
+    )? - schedule.claimed_amount)
+}
+
+/// Publish the context behind `error` and hand it back for returning.
+///
+/// The failing call is rolled back, so the event never reaches the ledger; it shows up in
+/// the diagnostic output of simulation and failed transactions, where tooling looks.
+fn diagnose(
+    env: &Env,
+    error: VestingError,
+    grant_id: u64,
+    required: i128,
+    available: i128,
+) -> VestingError {
+    env.events().publish(
+        (symbol_short!("diag"), error as u32),
+        ErrorDiagnostic {
+            grant_id,
+            required,
+            available,
+        },
+    );
+
+    error
+}
+
+fn period_claims(env: &Env, grant_id: u64) -> Option<(u64, i128)> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::PeriodClaims(grant_id))
+}
+
+/// What a capped schedule may still claim before its current period rolls over
+fn period_allowance(env: &Env, grant_id: u64, schedule: &VestingSchedule) -> i128 {
+    match period_claims(env, grant_id) {
+        Some((started, claimed))
+            if env.ledger().timestamp() < started.saturating_add(schedule.period_seconds) =>
+        {
+            (schedule.max_claim_per_period - claimed).max(0)
+        }
+        _ => schedule.max_claim_per_period,
+    }
+}
+
+fn current_ledger(env: &Env) -> (u32, u64) {
+    (env.ledger().sequence(), env.ledger().timestamp())
+}
+
+fn record_claim_ledger(env: &Env, grant_id: u64) {
+    env.storage().persistent().set(
+        &AcademyDataKey::LastClaimLedger(grant_id),
+        &current_ledger(env),
+    );
+}
+
+/// What a schedule still owes its beneficiary: the unclaimed amount, or for a revoked
+/// schedule only the part that had vested before the revoke
+fn unclaimed_obligation(env: &Env, grant_id: u64, schedule: &VestingSchedule) -> i128 {
+    if !schedule.revoked {
+        return schedule.amount - schedule.claimed_amount;
+    }
+
+    let vested_at_revoke: i128 = env
+        .storage()
+        .persistent()
+        .get(&AcademyDataKey::VestedAtRevoke(grant_id))
+        .unwrap_or(0);
+    (vested_at_revoke - schedule.claimed_amount).max(0)
+}
+
+/// Sum of granted-but-unclaimed tokens across schedules that can still be claimed
+fn outstanding_obligations(env: &Env) -> i128 {
+    let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+        .storage()
+        .persistent()
+        .get(&symbol_short!("sched"))
+        .unwrap_or_else(|| soroban_sdk::Map::new(env));
+
+    let mut outstanding = 0i128;
+    for (grant_id, schedule) in schedules.iter() {
+        outstanding += unclaimed_obligation(env, grant_id, &schedule);
+    }
+    outstanding
+}
+
+/// Persistent keys holding a schedule's side data, dropped with it by `remove_schedule`
+fn grant_side_keys(grant_id: u64) -> [AcademyDataKey; 10] {
+    [
+        AcademyDataKey::LastClaimLedger(grant_id),
+        AcademyDataKey::PendingTransfer(grant_id),
+        AcademyDataKey::VestedAtRevoke(grant_id),
+        AcademyDataKey::MilestoneOracle(grant_id),
+        AcademyDataKey::MilestoneAcceleration(grant_id),
+        AcademyDataKey::ClaimSplit(grant_id),
+        AcademyDataKey::PeriodClaims(grant_id),
+        AcademyDataKey::ClaimHistory(grant_id),
+        AcademyDataKey::LastStatus(grant_id),
+        AcademyDataKey::FundedAmount(grant_id),
+    ]
+}
+
+/// Delete a schedule, its beneficiary index entry and its side data
+fn remove_schedule(
+    env: &Env,
+    schedules: &mut soroban_sdk::Map<u64, VestingSchedule>,
+    grant_id: u64,
+    beneficiary: &Address,
+) {
+    if let Some(schedule) = schedules.get(grant_id) {
+        let unclaimed = unclaimed_obligation(env, grant_id, &schedule);
+        adjust_totals(env, -schedule.amount, -schedule.claimed_amount, -unclaimed);
+    }
+    schedules.remove(grant_id);
+
+    let storage = env.storage().persistent();
+    for key in grant_side_keys(grant_id) {
+        storage.remove(&key);
+    }
+
+    let mut ids = user_schedule_ids(env, beneficiary);
+    if let Some(index) = ids.first_index_of(grant_id) {
+        ids.remove(index);
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::UserScheduleIds(beneficiary.clone()), &ids);
+    }
+}
+
+/// Move a schedule to a new beneficiary, updating both user indexes and emitting a transfer event
+fn reassign_schedule(
+    env: &Env,
+    schedules: &mut soroban_sdk::Map<u64, VestingSchedule>,
+    grant_id: u64,
+    mut schedule: VestingSchedule,
+    new_beneficiary: &Address,
+) {
+    let from = schedule.beneficiary.clone();
+
+    let mut from_ids = user_schedule_ids(env, &from);
+    if let Some(index) = from_ids.first_index_of(grant_id) {
+        from_ids.remove(index);
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::UserScheduleIds(from.clone()), &from_ids);
+    }
+    index_user_schedule(env, new_beneficiary, grant_id);
+    // The old owner's payout split must not follow the schedule
+    env.storage()
+        .persistent()
+        .remove(&AcademyDataKey::ClaimSplit(grant_id));
+
+    schedule.beneficiary = new_beneficiary.clone();
+    schedules.set(grant_id, schedule);
+
+    env.events().publish(
+        (symbol_short!("transfer"),),
+        TransferEvent {
+            grant_id,
+            from,
+            to: new_beneficiary.clone(),
+            transferred_at: env.ledger().timestamp(),
+        },
+    );
+}
+
+fn has_pending_transfer(env: &Env, grant_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&AcademyDataKey::PendingTransfer(grant_id))
+}
+
+/// Move a schedule now, or queue it when governance requires a veto window.
+///
+/// Returns when the transfer takes (or took) effect.
+fn transfer_or_queue(
+    env: &Env,
+    schedules: &mut soroban_sdk::Map<u64, VestingSchedule>,
+    grant_id: u64,
+    schedule: VestingSchedule,
+    new_beneficiary: &Address,
+) -> u64 {
+    let now = env.ledger().timestamp();
+    let window = load_config(env).transfer_veto_window;
+    if window == 0 {
+        reassign_schedule(env, schedules, grant_id, schedule, new_beneficiary);
+        return now;
+    }
+
+    let pending = PendingTransfer {
+        new_beneficiary: new_beneficiary.clone(),
+        executable_at: now.saturating_add(window),
+    };
+    env.storage()
+        .persistent()
+        .set(&AcademyDataKey::PendingTransfer(grant_id), &pending);
+    env.events().publish(
+        (symbol_short!("xfer_req"),),
+        (grant_id, new_beneficiary.clone(), pending.executable_at),
+    );
+
+    pending.executable_at
+}
+
+/// Under `claim_on_transfer`, pay the vested portion to the current beneficiary first.
+///
+/// Skipped when nothing can be claimed right now (cliff, cooldown, cosign). Returns the
+/// schedule left to move, or `None` if the claim finished it.
+fn settle_before_transfer(
+    env: &Env,
+    schedules: &mut soroban_sdk::Map<u64, VestingSchedule>,
+    grant_id: u64,
+    schedule: VestingSchedule,
+) -> Result<Option<VestingSchedule>, VestingError> {
+    if !load_config(env).claim_on_transfer
+        || schedule.requires_cosign
+        || claimable_amount(env, grant_id, &schedule.beneficiary, &schedule).is_err()
+    {
+        return Ok(Some(schedule));
+    }
+
+    persist_schedules(env, schedules);
+    let payees = claim_payees(env, grant_id, &schedule.beneficiary);
+    execute_claim(env, grant_id, &schedule.beneficiary, &payees, false)?;
+    *schedules = load_schedules(env)?;
+
+    Ok(schedules.get(grant_id).filter(is_transferable))
+}
+
+fn schedule_status(env: &Env, config: &GlobalConfig, schedule: &VestingSchedule) -> ScheduleStatus {
+    if schedule.revoked {
+        return ScheduleStatus::Revoked;
+    }
+    if schedule.claimed {
+        return ScheduleStatus::Claimed;
+    }
+
+    let clock = vesting_clock(env, config, schedule);
+    if awaiting_start(schedule) || clock < schedule.start_time {
+        return ScheduleStatus::Pending;
+    }
+
+    let elapsed = clock - schedule.start_time;
+    if elapsed < schedule.cliff {
+        ScheduleStatus::Cliff
+    } else if elapsed < schedule.duration {
+        ScheduleStatus::Vesting
+    } else {
+        ScheduleStatus::FullyVested
+    }
+}
+
+/// Next start, cliff-end or full-vesting time still ahead of the vesting clock
+fn next_event(env: &Env, config: &GlobalConfig, schedule: &VestingSchedule) -> Option<u64> {
+    if schedule.claimed || schedule.revoked || awaiting_start(schedule) {
+        return None;
+    }
+
+    let clock = vesting_clock(env, config, schedule);
+    // Milestones are reported on the same clock the vesting math runs on
+    let paused_shift = clock_now(env).saturating_sub(clock);
+
+    let milestones = [
+        schedule.start_time,
+        schedule.start_time.saturating_add(schedule.cliff),
+        schedule.start_time.saturating_add(schedule.duration),
+    ];
+
+    milestones
+        .into_iter()
+        .find(|milestone| *milestone > clock)
+        .map(|milestone| milestone.saturating_add(paused_shift))
+}
+
+/// Whether the vesting clock has yet to start (pending acceptance or milestone)
+fn awaiting_start(schedule: &VestingSchedule) -> bool {
+    schedule.pending_acceptance || !schedule.milestone_met
+}
+
+/// A schedule's `(start_time, cliff, duration)` with its timeline scaled by `factor_bps`.
+///
+/// Started schedules scale around `pivot` (their vesting clock), so the fraction vested at
+/// the pivot is unchanged; future ones keep their start. `None` means fully vested already.
+fn rescaled_timeline(
+    schedule: &VestingSchedule,
+    pivot: u64,
+    factor_bps: u32,
+) -> Result<Option<(u64, u64, u64)>, VestingError> {
+    let scale = |seconds: u64| {
+        u64::try_from(seconds as u128 * factor_bps as u128 / BPS_DENOMINATOR as u128)
+            .map_err(|_| VestingError::InvalidSchedule)
+    };
+    let cliff = scale(schedule.cliff)?;
+    let duration = scale(schedule.duration)?;
+
+    if awaiting_start(schedule) || pivot < schedule.start_time {
+        return Ok(Some((schedule.start_time, cliff, duration)));
+    }
+    if pivot >= schedule.start_time.saturating_add(schedule.duration) {
+        return Ok(None);
+    }
+
+    let start = pivot
+        .checked_sub(scale(pivot - schedule.start_time)?)
+        .ok_or(VestingError::InvalidSchedule)?;
+    // The new end must stay ahead of the pivot, or everything would vest at once
+    if start.saturating_add(duration) <= pivot {
+        return Err(VestingError::InvalidSchedule);
+    }
+
+    Ok(Some((start, cliff, duration)))
+}
+
+fn store_schedule_data(env: &Env, grant_id: u64, data: Bytes) -> Result<(), VestingError> {
+    let mut schedules = load_schedules(env)?;
+    let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+    schedule.data = data;
+    schedules.set(grant_id, schedule);
+    persist_schedules(env, &schedules);
+
+    Ok(())
+}
+
+fn memo_fits(env: &Env, memo: &Symbol) -> bool {
+    SymbolStr::try_from_val(env, &memo.to_symbol_val())
+        .map(|memo| memo.len() <= MAX_MEMO_LEN)
+        .unwrap_or(false)
+}
+
+fn is_transferable(schedule: &VestingSchedule) -> bool {
+    schedule.transferable && !schedule.revoked && !schedule.claimed
+}
+
+/// Write back a schedule after a claim, finalizing it when configured and fully claimed
+fn store_claimed_schedule(
+    env: &Env,
+    schedules: &mut soroban_sdk::Map<u64, VestingSchedule>,
+    grant_id: u64,
+    schedule: VestingSchedule,
+    auto_finalize: bool,
+) {
+    // Revoked schedules already left the active index when they were revoked
+    if schedule.claimed && !schedule.revoked {
+        update_active(env, grant_id, false);
+    }
+
+    if auto_finalize && schedule.claimed {
+        remove_schedule(env, schedules, grant_id, &schedule.beneficiary);
+    } else {
+        schedules.set(grant_id, schedule);
+    }
+}
+
+/// Defensive check run after every schedule mutation
+pub(crate) fn assert_schedule_invariants(schedule: &VestingSchedule) -> Result<(), VestingError> {
+    if schedule.amount <= 0
+        || schedule.claimed_amount < 0
+        || schedule.claimed_amount > schedule.amount
+        || schedule.claimed != (schedule.claimed_amount == schedule.amount)
+    {
+        return Err(VestingError::InvariantViolation);
+    }
+
+    if schedule.cliff > schedule.duration
+        || schedule.start_time.checked_add(schedule.duration).is_none()
+    {
+        return Err(VestingError::InvariantViolation);
+    }
+
+    if !schedule.revoked && schedule.revoke_time != 0 {
+        return Err(VestingError::InvariantViolation);
+    }
+    // Revoking before start is allowed, but nothing can have been claimed by then
+    if schedule.revoked && schedule.revoke_time < schedule.start_time && schedule.claimed_amount > 0
+    {
+        return Err(VestingError::InvariantViolation);
+    }
+
+    Ok(())
+}
+
+/// Best-effort mint of receipt tokens for a claim; failures are logged, never fatal
+fn mint_receipt(env: &Env, grant_id: u64, beneficiary: &Address, amount: i128) {
+    let receipt_token: Option<Address> =
+        env.storage().instance().get(&AcademyDataKey::ReceiptToken);
+    let Some(receipt_token) = receipt_token else {
+        return;
+    };
+
+    let args: Vec<Val> = soroban_sdk::vec![env, beneficiary.into_val(env), amount.into_val(env)];
+    let minted = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+        &receipt_token,
+        &symbol_short!("mint"),
+        args,
+    );
+
+    if !matches!(minted, Ok(Ok(_))) {
+        env.events().publish(
+            (symbol_short!("rcpt_fail"),),
+            (grant_id, beneficiary.clone(), amount),
+        );
+    }
+}
+
+#[derive(Clone, Copy)]
+enum HookCall {
+    Grant,
+    Claim,
+    Revoke,
+}
+
+fn hooks(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&AcademyDataKey::Hooks)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Call every registered hook in order; a failing hook never blocks the others
+fn notify_hooks(env: &Env, call: HookCall, grant_id: u64, beneficiary: &Address, amount: i128) {
+    for hook in hooks(env).iter() {
+        let client = VestingHookClient::new(env, &hook);
+        let _ = match call {
+            HookCall::Grant => client.try_on_grant(&grant_id, beneficiary, &amount),
+            HookCall::Claim => client.try_on_claim(&grant_id, beneficiary, &amount),
+            HookCall::Revoke => client.try_on_revoke(&grant_id, beneficiary, &amount),
+        };
+    }
+
+    // The beneficiary's own notifier hears about their claims, after the global hooks
+    if let HookCall::Claim = call {
+        let notifier: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&AcademyDataKey::PersonalNotifier(beneficiary.clone()));
+        if let Some(notifier) = notifier {
+            let _ = VestingHookClient::new(env, &notifier).try_on_claim(
+                &grant_id,
+                beneficiary,
+                &amount,
+            );
+        }
+    }
+}
+
+/// Pay out a claim, routing `claim_fee_bps` of it to the treasury. Returns the fee.
+fn pay_claim(
+    env: &Env,
+    token_client: &soroban_sdk::token::Client,
+    destination: &Address,
+    amount: i128,
+) -> Result<i128, VestingError> {
+    let fee = amount * load_config(env).claim_fee_bps as i128 / BPS_DENOMINATOR as i128;
+    if fee > 0 {
+        let treasury = read_treasury(env).ok_or(VestingError::GovernanceNotSet)?;
+        token_client.transfer(&env.current_contract_address(), &treasury, &fee);
+    }
+    if amount > fee {
+        token_client.transfer(
+            &env.current_contract_address(),
+            destination,
+            &(amount - fee),
+        );
+    }
+
+    Ok(fee)
+}
+
+fn funded_amount(env: &Env, grant_id: u64) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::FundedAmount(grant_id))
+}
+
+/// Of `owed` unclaimed tokens leaving a schedule, what the contract actually holds for it:
+/// incrementally funded schedules are only backed up to what has been paid in
+fn backed_refund(env: &Env, grant_id: u64, schedule: &VestingSchedule, owed: i128) -> i128 {
+    match funded_amount(env, grant_id) {
+        Some(funded) => owed.min(funded - schedule.claimed_amount).max(0),
+        None => owed,
+    }
+}
+
+/// Move `amount` of the reward token from `funder` into the contract, checking the balance
+/// first so a short funder gets `InsufficientBalance` rather than a token trap
+fn pull_funding(env: &Env, funder: &Address, amount: i128) -> Result<(), VestingError> {
+    let token = read_token(env).ok_or(VestingError::TokenNotSet)?;
+    let token_client = soroban_sdk::token::Client::new(env, &token);
+    if token_client.balance(funder) < amount {
+        return Err(VestingError::InsufficientBalance);
+    }
+    token_client.transfer(funder, &env.current_contract_address(), &amount);
+
+    Ok(())
+}
+
+fn claim_history(env: &Env, grant_id: u64) -> Vec<ClaimRecord> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::ClaimHistory(grant_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Apply a claim of `amount` to a schedule, flagging it once fully claimed
+fn record_claim(env: &Env, grant_id: u64, schedule: &mut VestingSchedule, amount: i128) {
+    schedule.claimed_amount += amount;
+    schedule.claimed = schedule.claimed_amount >= schedule.amount;
+    adjust_totals(env, 0, amount, -amount);
+
+    let mut history = claim_history(env, grant_id);
+    if history.len() >= MAX_CLAIM_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(ClaimRecord {
+        amount,
+        claimed_at: env.ledger().timestamp(),
+        ledger: env.ledger().sequence(),
+    });
+    env.storage()
+        .persistent()
+        .set(&AcademyDataKey::ClaimHistory(grant_id), &history);
+
+    // A claim after the period has lapsed opens a new one
+    if schedule.period_seconds > 0 {
+        let now = env.ledger().timestamp();
+        let usage = match period_claims(env, grant_id) {
+            Some((started, claimed)) if now < started.saturating_add(schedule.period_seconds) => {
+                (started, claimed + amount)
+            }
+            _ => (now, amount),
+        };
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::PeriodClaims(grant_id), &usage);
+    }
+}
+
+/// Whether `grant_ids` names any schedule more than once
+fn has_duplicate_ids(env: &Env, grant_ids: &Vec<u64>) -> bool {
+    let mut seen = Vec::new(env);
+    for grant_id in grant_ids.iter() {
+        if seen.contains(grant_id) {
+            return true;
+        }
+        seen.push_back(grant_id);
+    }
+    false
+}
+
+/// Publish the `summarize_batches` stand-in for a batch's per-schedule events
+fn publish_batch_event(env: &Env, operation: Symbol, count: u32, total_amount: i128) {
+    env.events().publish(
+        (symbol_short!("batch"),),
+        BatchEvent {
+            operation,
+            count,
+            total_amount,
+        },
+    );
+}
+
+fn allowed_destinations(env: &Env, beneficiary: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::AllowedDestinations(beneficiary.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// The beneficiary itself is always allowed; others only if listed, once a list exists
+fn destination_allowed(env: &Env, beneficiary: &Address, destination: &Address) -> bool {
+    if destination == beneficiary {
+        return true;
+    }
+    let allowed = allowed_destinations(env, beneficiary);
+    allowed.is_empty() || allowed.contains(destination)
+}
+
+/// The beneficiary's chosen destination, or the beneficiary if none is set or it is not allowed
+fn default_destination(env: &Env, beneficiary: &Address) -> Address {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::ClaimDestination(beneficiary.clone()))
+        .filter(|destination| destination_allowed(env, beneficiary, destination))
+        .unwrap_or_else(|| beneficiary.clone())
+}
+
+/// Where a default claim pays out: the schedule's split if set (and every payee is allowed),
+/// else the default destination
+fn claim_payees(env: &Env, grant_id: u64, beneficiary: &Address) -> Vec<(Address, u32)> {
+    env.storage()
+        .persistent()
+        .get(&AcademyDataKey::ClaimSplit(grant_id))
+        .filter(|splits: &Vec<(Address, u32)>| {
+            splits
+                .iter()
+                .all(|(payee, _)| destination_allowed(env, beneficiary, &payee))
+        })
+        .unwrap_or_else(|| sole_payee(env, default_destination(env, beneficiary)))
+}
+
+fn sole_payee(env: &Env, destination: Address) -> Vec<(Address, u32)> {
+    soroban_sdk::vec![env, (destination, BPS_DENOMINATOR)]
+}
+
+/// Pay a claim across `payees` by bps, the last payee taking any rounding remainder.
+///
+/// Returns the total fee. Split payouts emit one `ClaimEvent` per destination.
+fn pay_claim_split(
+    env: &Env,
+    token_client: &soroban_sdk::token::Client,
+    grant_id: u64,
+    beneficiary: &Address,
+    payees: &Vec<(Address, u32)>,
+    amount: i128,
+) -> Result<i128, VestingError> {
+    let split = payees.len() > 1;
+    let mut fee = 0i128;
+    let mut paid = 0i128;
+    for (index, (payee, bps)) in payees.iter().enumerate() {
+        let share = if index as u32 + 1 == payees.len() {
+            amount - paid
+        } else {
+            amount * bps as i128 / BPS_DENOMINATOR as i128
+        };
+        paid += share;
+
+        let share_fee = pay_claim(env, token_client, &payee, share)?;
+        fee += share_fee;
+        if split {
+            env.events().publish(
+                (symbol_short!("claim"), payee),
+                ClaimEvent {
+                    grant_id,
+                    beneficiary: beneficiary.clone(),
+                    amount: share,
+                    fee: share_fee,
+                    claimed_at: env.ledger().timestamp(),
+                },
+            );
+        }
+    }
+
+    Ok(fee)
+}
+
+fn execute_claim(
+    env: &Env,
+    grant_id: u64,
+    beneficiary: &Address,
+    payees: &Vec<(Address, u32)>,
+    cosigned: bool,
+) -> Result<ClaimReceipt, VestingError> {
+    let mut schedules = load_schedules(env)?;
+    let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+    if schedule.requires_cosign && !cosigned {
+        return Err(VestingError::CosignRequired);
+    }
+    let vested_amount = match claimable_amount(env, grant_id, beneficiary, &schedule) {
+        Err(VestingError::BelowMinimumClaim) => {
+            let config = load_config(env);
+            let available = unclaimed_vested(env, &config, grant_id, &schedule)?;
+            return Err(diagnose(
+                env,
+                VestingError::BelowMinimumClaim,
+                grant_id,
+                config.min_claim,
+                available,
+            ));
+        }
+        result => result?,
+    };
+
+    // Verify contract has sufficient balance
+    let token = read_token(env).ok_or(VestingError::TokenNotSet)?;
+
+    let token_client = soroban_sdk::token::Client::new(env, &token);
+    let balance = token_client.balance(&env.current_contract_address());
+
+    if balance < vested_amount {
+        return Err(diagnose(
+            env,
+            VestingError::InsufficientBalance,
+            grant_id,
+            vested_amount,
+            balance,
+        ));
+    }
+
+    // Record the claim before transferring (atomic operation)
+    record_claim(env, grant_id, &mut schedule, vested_amount);
+    assert_schedule_invariants(&schedule)?;
+    record_claim_ledger(env, grant_id);
+    store_claimed_schedule(
+        env,
+        &mut schedules,
+        grant_id,
+        schedule.clone(),
+        load_config(env).auto_finalize,
+    );
+    persist_schedules(env, &schedules);
+
+    // Transfer tokens
+    let fee = pay_claim_split(
+        env,
+        &token_client,
+        grant_id,
+        beneficiary,
+        payees,
+        vested_amount,
+    )?;
+    mint_receipt(env, grant_id, beneficiary, vested_amount - fee);
+    notify_hooks(env, HookCall::Claim, grant_id, beneficiary, vested_amount);
+
+    let current_time = env.ledger().timestamp();
+    env.storage().persistent().set(
+        &AcademyDataKey::LastClaim(beneficiary.clone()),
+        &current_time,
+    );
+
+    // Emit claim event (split payouts already emitted one per destination)
+    if payees.len() == 1 {
+        let claim_event = ClaimEvent {
+            grant_id,
+            beneficiary: beneficiary.clone(),
+            amount: vested_amount,
+            fee,
+            claimed_at: current_time,
+        };
+
+        env.events().publish((symbol_short!("claim"),), claim_event);
+    }
+
+    // Emit VestingClaimed event (for indexer)
+    let vesting_claimed = VestingClaimed {
+        grant_id,
+        beneficiary: beneficiary.clone(),
+        amount: vested_amount,
+        claimed_at: current_time,
+    };
+
+    env.events()
+        .publish((symbol_short!("v_claimed"),), vesting_claimed);
+
+    Ok(ClaimReceipt {
+        grant_id,
+        amount: vested_amount,
+        total_claimed: schedule.claimed_amount,
+        remaining: schedule.amount - schedule.claimed_amount,
+        fee,
+        claimed_at: current_time,
+    })
+}
+
+#[contractimpl]
+impl AcademyVestingContract {
+    /// Initialize the vesting contract with admin and governance roles.
+    ///
+    /// `reward_token` may be any SEP-41 token, including the native XLM asset contract.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        reward_token: Address,
+        governance: Address,
+        cb_config: CircuitBreakerConfig,
+    ) -> Result<(), VestingError> {
+        // Check if already initialized
+        let init_key = symbol_short!("init");
+        if env.storage().persistent().has(&init_key) {
+            return Err(VestingError::Unauthorized);
+        }
+
+        // Set initialization flag
+        env.storage().persistent().set(&init_key, &true);
+
+        // Store admin
+        env.storage().instance().set(&AcademyDataKey::Admin, &admin);
+
+        // Store reward token and the decimals its amounts are expressed in
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::RewardToken, &reward_token);
+        let decimals = soroban_sdk::token::Client::new(&env, &reward_token).decimals();
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::TokenDecimals, &decimals);
+
+        // Store governance address
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::Governance, &governance);
+
+        // Store roles for shared GovernanceManager compatibility
+        let mut roles = soroban_sdk::Map::new(&env);
+        roles.set(admin.clone(), shared::governance::GovernanceRole::Admin);
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("roles"), &roles);
+
+        // Initialize grant counter
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::GrantCounter, &0u64);
+
+        // Fresh deployments start on the current storage layout
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::StorageVersion, &STORAGE_VERSION);
+
+        // Initialize circuit breaker
+        CircuitBreaker::init(&env, cb_config);
+
+        Ok(())
+    }
+
+    /// Grant a vesting schedule to a beneficiary
+    pub fn grant_vesting(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start_time: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<u64, VestingError> {
+        Self::create_grant(env, admin, beneficiary, amount, start_time, cliff, duration)
+    }
+
+    /// Fund and create several schedules at once (admin only).
+    ///
+    /// The admin pays the summed amount in a single transfer; if the contract receives
+    /// less than that, no schedule is created. Returns the new grant IDs in order.
+    pub fn grant_batch(
+        env: Env,
+        admin: Address,
+        grants: Vec<GrantRequest>,
+    ) -> Result<Vec<u64>, VestingError> {
+        admin.require_auth();
+        require_admin(&env, &admin, "grant_vesting")?;
+
+        if grants.len() > MAX_BATCH_GRANTS {
+            return Err(VestingError::BatchTooLarge);
+        }
+
+        let mut total = 0i128;
+        for grant in grants.iter() {
+            if grant.beneficiary == env.current_contract_address() {
+                return Err(VestingError::InvalidBeneficiary);
+            }
+            if grant.amount <= 0 {
+                return Err(VestingError::InvalidAmount);
+            }
+            total = total.checked_add(grant.amount).ok_or_else(|| {
+                diagnose(
+                    &env,
+                    VestingError::InvalidSchedule,
+                    0,
+                    grant.amount,
+                    i128::MAX - total,
+                )
+            })?;
+        }
+
+        let min_start = read_min_start(&env);
+        if grants.iter().any(|grant| grant.start_time < min_start) {
+            return Err(VestingError::StartTooEarly);
+        }
+        let min_duration = read_min_duration(&env);
+        if grants.iter().any(|grant| grant.duration < min_duration) {
+            return Err(VestingError::DurationTooShort);
+        }
+
+        let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        let contract = env.current_contract_address();
+        if token_client.balance(&admin) < total {
+            return Err(VestingError::InsufficientBalance);
+        }
+        let balance_before = token_client.balance(&contract);
+        if total > 0 {
+            token_client.transfer(&admin, &contract, &total);
+        }
+        if token_client.balance(&contract) - balance_before < total {
+            return Err(VestingError::InsufficientBalance);
+        }
+
+        let mut grant_ids = Vec::new(&env);
+        for grant in grants.iter() {
+            grant_ids.push_back(Self::record_grant(
+                env.clone(),
+                admin.clone(),
+                grant.beneficiary,
+                grant.amount,
+                grant.start_time,
+                grant.cliff,
+                grant.duration,
+            )?);
+        }
+
+        Ok(grant_ids)
+    }
+
+    /// Store or replace a named grant template (governance only)
+    pub fn create_template(
+        env: Env,
+        governance: Address,
+        name: Symbol,
+        template: GrantTemplate,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "create_template");
+
+        if template.cliff > template.duration {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let mut names = Self::list_templates(env.clone());
+        if !names.contains(&name) {
+            names.push_back(name.clone());
+            env.storage()
+                .instance()
+                .set(&AcademyDataKey::TemplateNames, &names);
+        }
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::Template(name), &template);
+
+        Ok(())
+    }
+
+    /// Grant `amount` using a template's timing, starting `start_delay` seconds from now
+    pub fn grant_from_template(
+        env: Env,
+        admin: Address,
+        name: Symbol,
+        beneficiary: Address,
+        amount: i128,
+    ) -> Result<u64, VestingError> {
+        let template =
+            Self::get_template(env.clone(), name).ok_or(VestingError::TemplateNotFound)?;
+        let start_time = env
+            .ledger()
+            .timestamp()
+            .saturating_add(template.start_delay);
+
+        Self::grant_vesting(
+            env,
+            admin,
+            beneficiary,
+            amount,
+            start_time,
+            template.cliff,
+            template.duration,
+        )
+    }
+
+    /// Grant a schedule starting now with the cliff, duration, tail and curve of `template_id`
+    /// (admin only)
+    pub fn clone_schedule(
+        env: Env,
+        admin: Address,
+        template_id: u64,
+        beneficiary: Address,
+        amount: i128,
+    ) -> Result<u64, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let source = schedules
+            .get(template_id)
+            .ok_or(VestingError::GrantNotFound)?;
+        let start_time = env.ledger().timestamp();
+
+        let grant_id = Self::grant_vesting_with_tail(
+            env.clone(),
+            admin,
+            beneficiary,
+            amount,
+            start_time,
+            source.cliff,
+            source.duration,
+            source.tail_bps,
+        )?;
+        if source.curve != VestingCurve::Linear {
+            let mut schedules = load_schedules(&env)?;
+            let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+            schedule.curve = source.curve;
+            schedules.set(grant_id, schedule);
+            persist_schedules(&env, &schedules);
+        }
+
+        Ok(grant_id)
+    }
+
+    /// Serialize a schedule as XDR for `import_schedule` on another deployment.
+    ///
+    /// Covers every `VestingSchedule` field; per-grant side data (claim splits, period usage,
+    /// pending transfers) stays behind.
+    pub fn export_schedule(env: Env, grant_id: u64) -> Result<Bytes, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        Ok(schedule.to_xdr(&env))
+    }
+
+    /// Store a schedule produced by `export_schedule` under the next grant ID (admin only).
+    ///
+    /// Claimed amount and timestamps carry over unchanged; `pause_offset` is rebased onto this
+    /// contract's paused time. Revoked schedules, and any that break the schedule invariants or
+    /// this contract's `min_start` / `min_duration` floors, fail with `InvalidSchedule`.
+    pub fn import_schedule(env: Env, admin: Address, encoded: Bytes) -> Result<u64, VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "import_schedule")?;
+        log_admin_action(&env, &admin, "import_schedule");
+
+        let mut schedule =
+            VestingSchedule::from_xdr(&env, &encoded).map_err(|_| VestingError::InvalidSchedule)?;
+        if schedule.amount <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        // Hold the encoding to the same rules a grant made here would meet
+        let span = schedule.duration.saturating_sub(schedule.cliff);
+        let curve_fits = match schedule.curve {
+            VestingCurve::Linear => true,
+            VestingCurve::Accruing(apy_bps) => {
+                apy_bps > 0 && span > 0 && accrued_fraction(apy_bps, 0, span).is_some()
+            }
+            VestingCurve::Stepped(tranches) => tranches > 0 && tranches as u64 <= span,
+        };
+        if schedule.revoked
+            || assert_schedule_invariants(&schedule).is_err()
+            || (!awaiting_start(&schedule) && schedule.start_time < read_min_start(&env))
+            || schedule.duration < read_min_duration(&env)
+            || schedule.tail_bps > BPS_DENOMINATOR
+            || !curve_fits
+        {
+            return Err(VestingError::InvalidSchedule);
+        }
+        if schedule.beneficiary == env.current_contract_address() {
+            return Err(VestingError::InvalidBeneficiary);
+        }
+        if schedule.data.len() > MAX_SCHEDULE_DATA || !memo_fits(&env, &schedule.memo) {
+            return Err(VestingError::DataTooLarge);
+        }
+        schedule.pause_offset = paused_seconds(&env);
+
+        let mut schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let grant_id = next_grant_id(&env, &schedules);
+        schedules.set(grant_id, schedule.clone());
+        persist_schedules(&env, &schedules);
+
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::GrantCounter, &grant_id);
+        index_user_schedule(&env, &schedule.beneficiary, grant_id);
+        if !schedule.claimed {
+            update_active(&env, grant_id, true);
+        }
+        let unclaimed = schedule.amount - schedule.claimed_amount;
+        adjust_totals(&env, schedule.amount, schedule.claimed_amount, unclaimed);
+
+        env.events()
+            .publish((symbol_short!("import"),), (grant_id, schedule.beneficiary));
+
+        Ok(grant_id)
+    }
+
+    /// Grant a schedule carrying an integrator data blob of at most 256 bytes (admin only)
+    pub fn grant_with_data(
+        env: Env,
+        admin: Address,
+        grant: GrantRequest,
+        data: Bytes,
+    ) -> Result<u64, VestingError> {
+        if data.len() > MAX_SCHEDULE_DATA {
+            return Err(VestingError::DataTooLarge);
+        }
+
+        let grant_id = Self::grant_vesting(
+            env.clone(),
+            admin,
+            grant.beneficiary,
+            grant.amount,
+            grant.start_time,
+            grant.cliff,
+            grant.duration,
+        )?;
+        store_schedule_data(&env, grant_id, data)?;
+
+        Ok(grant_id)
+    }
+
+    /// Replace a schedule's data blob, at most 256 bytes (beneficiary only)
+    pub fn set_schedule_data(env: Env, grant_id: u64, data: Bytes) -> Result<(), VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.beneficiary.require_auth();
+
+        if data.len() > MAX_SCHEDULE_DATA {
+            return Err(VestingError::DataTooLarge);
+        }
+
+        store_schedule_data(&env, grant_id, data)
+    }
+
+    /// Set or replace a schedule's memo, at most 16 characters (beneficiary only)
+    pub fn set_memo(env: Env, grant_id: u64, memo: Symbol) -> Result<(), VestingError> {
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.beneficiary.require_auth();
+
+        if !memo_fits(&env, &memo) {
+            return Err(VestingError::DataTooLarge);
+        }
+
+        schedule.memo = memo;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(())
+    }
+
+    /// Query a grant template by name
+    pub fn get_template(env: Env, name: Symbol) -> Option<GrantTemplate> {
+        env.storage()
+            .persistent()
+            .get(&AcademyDataKey::Template(name))
+    }
+
+    /// Query the names of all stored templates, in creation order
+    pub fn list_templates(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&AcademyDataKey::TemplateNames)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Grant a schedule at most once per `idempotency_key`.
+    ///
+    /// Replaying a known key returns the original grant ID without creating another schedule.
+    #[allow(clippy::too_many_arguments)]
+    pub fn grant_vesting_idempotent(
+        env: Env,
+        admin: Address,
+        idempotency_key: BytesN<32>,
+        beneficiary: Address,
+        amount: i128,
+        start_time: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<u64, VestingError> {
+        let key = AcademyDataKey::IdempotencyKey(idempotency_key);
+        let recorded: Option<(u64, u64)> = env.storage().persistent().get(&key);
+        if let Some((grant_id, _)) = recorded {
+            admin.require_auth();
+            return Ok(grant_id);
+        }
+
+        let grant_id = Self::grant_vesting(
+            env.clone(),
+            admin,
+            beneficiary,
+            amount,
+            start_time,
+            cliff,
+            duration,
+        )?;
+        env.storage()
+            .persistent()
+            .set(&key, &(grant_id, env.ledger().timestamp()));
+
+        Ok(grant_id)
+    }
+
+    /// Remove idempotency keys older than `IDEMPOTENCY_WINDOW` (admin only).
+    ///
+    /// Unknown or still-fresh keys are left alone. Returns how many keys were removed.
+    pub fn prune_idempotency_keys(
+        env: Env,
+        admin: Address,
+        keys: Vec<BytesN<32>>,
+    ) -> Result<u32, VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "prune_idempotency_keys")?;
+        log_admin_action(&env, &admin, "prune_idempotency_keys");
+
+        let now = env.ledger().timestamp();
+        let mut removed = 0u32;
+        for idempotency_key in keys.iter() {
+            let key = AcademyDataKey::IdempotencyKey(idempotency_key);
+            let recorded: Option<(u64, u64)> = env.storage().persistent().get(&key);
+            if let Some((_, recorded_at)) = recorded {
+                if now.saturating_sub(recorded_at) >= IDEMPOTENCY_WINDOW {
+                    env.storage().persistent().remove(&key);
+                    removed += 1;
+                }
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Internal helper: authorize the admin, then validate and store a new schedule
+    fn create_grant(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start_time: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<u64, VestingError> {
+        admin.require_auth();
+        require_admin(&env, &admin, "grant_vesting")?;
+
+        Self::record_grant(env, admin, beneficiary, amount, start_time, cliff, duration)
+    }
+
+    /// Internal helper: authorize the admin, then store a schedule whose start is set later
+    /// (on acceptance or milestone), so the start floor is checked then instead
+    fn create_deferred_grant(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<u64, VestingError> {
+        admin.require_auth();
+        require_admin(&env, &admin, "grant_vesting")?;
+
+        let grant = GrantRequest {
+            beneficiary,
+            amount,
+            start_time: 0,
+            cliff,
+            duration,
+        };
+        Self::store_grant(env, admin, grant, None, true)
+    }
+
+    /// Grant a schedule under a caller-chosen ID instead of the next counter value (admin only).
+    ///
+    /// Fails with `ScheduleIdTaken` if the ID was ever issued, even to a schedule since
+    /// deleted; 0 is never valid.
+    /// Counter-assigned grants skip over IDs taken this way, but IDs above the counter are
+    /// not reached by the ID-range walks (`get_all_schedule_ids_paged`, `bump_all_ttls`).
+    pub fn grant_with_id(
+        env: Env,
+        admin: Address,
+        derived_id: u64,
+        grant: GrantRequest,
+    ) -> Result<u64, VestingError> {
+        admin.require_auth();
+        require_admin(&env, &admin, "grant_vesting")?;
+
+        Self::store_grant(env, admin, grant, Some(derived_id), false)
+    }
+
+    /// Internal helper: validate and store a new schedule, emitting grant events.
+    ///
+    /// The caller must already have required `admin`'s authorization and passed `require_admin`.
+    fn record_grant(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start_time: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<u64, VestingError> {
+        let grant = GrantRequest {
+            beneficiary,
+            amount,
+            start_time,
+            cliff,
+            duration,
+        };
+        Self::store_grant(env, admin, grant, None, false)
+    }
+
+    /// Internal helper behind `record_grant`, storing under `derived_id` when one is given.
+    ///
+    /// `deferred_start` skips the start floor for placeholder starts set on acceptance or milestone.
+    fn store_grant(
+        env: Env,
+        admin: Address,
+        grant: GrantRequest,
+        derived_id: Option<u64>,
+        deferred_start: bool,
+    ) -> Result<u64, VestingError> {
+        let GrantRequest {
+            beneficiary,
+            amount,
+            start_time,
+            cliff,
+            duration,
+        } = grant;
+
+        // Check pause state via CircuitBreaker
+        CircuitBreaker::require_not_paused(&env, symbol_short!("grant"));
+
+        log_admin_action(&env, &admin, "grant_vesting");
+
+        // Validate schedule
+        if beneficiary == env.current_contract_address() {
+            return Err(VestingError::InvalidBeneficiary);
+        }
+        if amount <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        // Reject backdated schedules
+        if !deferred_start && start_time < read_min_start(&env) {
+            return Err(VestingError::StartTooEarly);
+        }
+        if cliff > duration {
+            return Err(VestingError::InvalidSchedule);
+        }
+        if duration < read_min_duration(&env) {
+            return Err(VestingError::DurationTooShort);
+        }
+
+        // Create vesting schedule
+        let schedule = VestingSchedule {
+            beneficiary: beneficiary.clone(),
+            amount,
+            start_time,
+            cliff,
+            duration,
+            claimed: false,
+            claimed_amount: 0,
+            revoked: false,
+            revoke_time: 0,
+            pause_offset: paused_seconds(&env),
+            pending_acceptance: false,
+            transferable: true,
+            requires_cosign: false,
+            granted_by: admin.clone(),
+            milestone_met: true,
+            tail_bps: 0,
+            created_at: env.ledger().timestamp(),
+            max_claim_per_period: 0,
+            period_seconds: 0,
+            data: Bytes::new(&env),
+            curve: VestingCurve::Linear,
+            memo: Symbol::new(&env, ""),
+        };
+
+        // Store schedule
+        let schedules_key = symbol_short!("sched");
+        let mut schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&schedules_key)
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        // Get next grant ID
+        let next_id = match derived_id {
+            Some(0) => return Err(VestingError::InvalidSchedule),
+            Some(id) if id_issued(&env, &schedules, id) => {
+                return Err(VestingError::ScheduleIdTaken)
+            }
+            Some(id) => id,
+            None => next_grant_id(&env, &schedules),
+        };
+
+        let config = load_config(&env);
+        let mut live_for_user = 0u32;
+        let mut outstanding = 0i128;
+        for (_, existing) in schedules.iter() {
+            if existing.claimed || existing.revoked {
+                continue;
+            }
+            outstanding += existing.amount - existing.claimed_amount;
+            if existing.beneficiary == beneficiary {
+                live_for_user += 1;
+            }
+        }
+
+        if config.max_schedules_per_user > 0 && live_for_user >= config.max_schedules_per_user {
+            return Err(VestingError::TooManySchedules);
+        }
+        if config.commitment_cap > 0 && outstanding + amount > config.commitment_cap {
+            return Err(VestingError::CommitmentCapExceeded);
+        }
+        if config.require_collateral {
+            let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+            let balance = soroban_sdk::token::Client::new(&env, &token)
+                .balance(&env.current_contract_address());
+            if balance < outstanding + amount {
+                return Err(VestingError::UnderCollateralized);
+            }
+        }
+
+        schedules.set(next_id, schedule);
+        env.storage().persistent().set(&schedules_key, &schedules);
+
+        // Update counter
+        if derived_id.is_none() {
+            env.storage()
+                .instance()
+                .set(&AcademyDataKey::GrantCounter, &next_id);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&AcademyDataKey::IssuedId(next_id), &true);
+        }
+        index_user_schedule(&env, &beneficiary, next_id);
+        update_active(&env, next_id, true);
+        adjust_totals(&env, amount, 0, amount);
+        record_used_token(&env, read_token(&env).ok_or(VestingError::TokenNotSet)?);
+
+        let current_timestamp = env.ledger().timestamp();
+
+        // Emit grant event
+        let grant_event = GrantEvent {
+            grant_id: next_id,
+            beneficiary: beneficiary.clone(),
+            amount,
+            start_time,
+            cliff,
+            duration,
+            granted_at: current_timestamp,
+            granted_by: admin.clone(),
+        };
+
+        env.events().publish((symbol_short!("grant"),), grant_event);
+        notify_hooks(&env, HookCall::Grant, next_id, &beneficiary, amount);
+
+        // Emit CredentialIssued event (for indexer compatibility)
+        let credential_event = CredentialIssued {
+            grant_id: next_id,
+            beneficiary,
+            amount,
+            start_time,
+            cliff,
+            duration,
+            granted_at: current_timestamp,
+            granted_by: admin,
+        };
+
+        env.events()
+            .publish((symbol_short!("cred_iss"),), credential_event);
+
+        Ok(next_id)
+    }
+
+    /// Grant a vesting schedule whose cliff is the configured `default_cliff_bps` of its duration
+    pub fn grant_vesting_default_cliff(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start_time: u64,
+        duration: u64,
+    ) -> Result<u64, VestingError> {
+        let cliff_bps = load_config(&env).default_cliff_bps;
+        let cliff = (duration as u128 * cliff_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+
+        Self::grant_vesting(env, admin, beneficiary, amount, start_time, cliff, duration)
+    }
+
+    /// Grant a linear schedule whose last `tail_bps` of the amount unlocks only at the end.
+    ///
+    /// The cliff (as a share of duration) plus the tail may not exceed 100%.
+    #[allow(clippy::too_many_arguments)]
+    pub fn grant_vesting_with_tail(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start_time: u64,
+        cliff: u64,
+        duration: u64,
+        tail_bps: u32,
+    ) -> Result<u64, VestingError> {
+        let cliff_bps = if duration == 0 {
+            0
+        } else {
+            cliff as u128 * BPS_DENOMINATOR as u128 / duration as u128
+        };
+        if cliff_bps + tail_bps as u128 > BPS_DENOMINATOR as u128 {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let grant_id = Self::grant_vesting(
+            env.clone(),
+            admin,
+            beneficiary,
+            amount,
+            start_time,
+            cliff,
+            duration,
+        )?;
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.tail_bps = tail_bps;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(grant_id)
+    }
+
+    /// Grant a schedule that unlocks along a daily-compounding curve at `apy_bps` (admin only).
+    ///
+    /// Nothing unlocks before the first full day after the cliff, and everything has unlocked by
+    /// the end. Rates whose growth over the schedule would exceed `MAX_ACCRUAL_GROWTH` are refused.
+    pub fn grant_accruing(
+        env: Env,
+        admin: Address,
+        grant: GrantRequest,
+        apy_bps: u32,
+    ) -> Result<u64, VestingError> {
+        let span = grant.duration.saturating_sub(grant.cliff);
+        if apy_bps == 0 || span == 0 || accrued_fraction(apy_bps, 0, span).is_none() {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let grant_id = Self::grant_vesting(
+            env.clone(),
+            admin,
+            grant.beneficiary,
+            grant.amount,
+            grant.start_time,
+            grant.cliff,
+            grant.duration,
+        )?;
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.curve = VestingCurve::Accruing(apy_bps);
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(grant_id)
+    }
+
+    /// Grant a schedule that unlocks in `tranches` equal steps after the cliff (admin only).
+    ///
+    /// Tranche `k` unlocks `k / tranches` of the way from the cliff to the end; each step must
+    /// be at least a second long.
+    pub fn grant_stepped(
+        env: Env,
+        admin: Address,
+        grant: GrantRequest,
+        tranches: u32,
+    ) -> Result<u64, VestingError> {
+        let span = grant.duration.saturating_sub(grant.cliff);
+        if tranches == 0 || tranches as u64 > span {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let grant_id = Self::grant_vesting(
+            env.clone(),
+            admin,
+            grant.beneficiary,
+            grant.amount,
+            grant.start_time,
+            grant.cliff,
+            grant.duration,
+        )?;
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.curve = VestingCurve::Stepped(tranches);
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(grant_id)
+    }
+
+    /// Grant a schedule funded incrementally, paying in `initial_funding` now (admin only).
+    ///
+    /// Claims release at most what has been funded; top it up with `fund_schedule`.
+    pub fn grant_partially_funded(
+        env: Env,
+        admin: Address,
+        grant: GrantRequest,
+        initial_funding: i128,
+    ) -> Result<u64, VestingError> {
+        if initial_funding < 0 || initial_funding > grant.amount {
+            return Err(VestingError::InvalidAmount);
+        }
+
+        let grant_id = Self::grant_vesting(
+            env.clone(),
+            admin.clone(),
+            grant.beneficiary,
+            grant.amount,
+            grant.start_time,
+            grant.cliff,
+            grant.duration,
+        )?;
+        if initial_funding > 0 {
+            pull_funding(&env, &admin, initial_funding)?;
+        }
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::FundedAmount(grant_id), &initial_funding);
+
+        Ok(grant_id)
+    }
+
+    /// Pay more of an incrementally funded schedule's committed total in (admin only)
+    pub fn fund_schedule(
+        env: Env,
+        admin: Address,
+        grant_id: u64,
+        amount: i128,
+    ) -> Result<i128, VestingError> {
+        admin.require_auth();
+        require_admin(&env, &admin, "fund_schedule")?;
+        log_admin_action(&env, &admin, "fund_schedule");
+
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        let funded = funded_amount(&env, grant_id).ok_or(VestingError::InvalidSchedule)?;
+        if amount <= 0 || funded + amount > schedule.amount {
+            return Err(VestingError::InvalidAmount);
+        }
+
+        pull_funding(&env, &admin, amount)?;
+        let funded = funded + amount;
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::FundedAmount(grant_id), &funded);
+        env.events()
+            .publish((symbol_short!("funded"),), (grant_id, amount, funded));
+
+        Ok(funded)
+    }
+
+    /// Query how much of an incrementally funded schedule has been paid in (`None` otherwise)
+    pub fn get_funded_amount(env: Env, grant_id: u64) -> Option<i128> {
+        funded_amount(&env, grant_id)
+    }
+
+    /// Grant a schedule that starts vesting only once the beneficiary accepts it.
+    ///
+    /// The stored `start_time` is a placeholder (0) until `accept_grant` sets it.
+    pub fn grant_vesting_on_acceptance(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<u64, VestingError> {
+        let grant_id =
+            Self::create_deferred_grant(env.clone(), admin, beneficiary, amount, cliff, duration)?;
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.pending_acceptance = true;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(grant_id)
+    }
+
+    /// Grant a schedule gated on a milestone confirmed by governance or `milestone_oracle`.
+    ///
+    /// Nothing vests before `mark_milestone`; accrual then runs from the milestone time.
+    pub fn grant_vesting_on_milestone(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        cliff: u64,
+        duration: u64,
+        milestone_oracle: Option<Address>,
+    ) -> Result<u64, VestingError> {
+        let grant_id =
+            Self::create_deferred_grant(env.clone(), admin, beneficiary, amount, cliff, duration)?;
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.milestone_met = false;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        if let Some(oracle) = milestone_oracle {
+            env.storage()
+                .persistent()
+                .set(&AcademyDataKey::MilestoneOracle(grant_id), &oracle);
+        }
+
+        Ok(grant_id)
+    }
+
+    /// Grant a normally vesting schedule whose milestone ends it `acceleration` seconds early.
+    ///
+    /// Unlike `grant_vesting_on_milestone`, vesting is not gated; `mark_milestone` (by
+    /// governance or `milestone_oracle`) only shortens the duration, never below the cliff.
+    pub fn grant_with_milestone_boost(
+        env: Env,
+        admin: Address,
+        grant: GrantRequest,
+        acceleration: u64,
+        milestone_oracle: Option<Address>,
+    ) -> Result<u64, VestingError> {
+        let grant_id = Self::grant_vesting(
+            env.clone(),
+            admin,
+            grant.beneficiary,
+            grant.amount,
+            grant.start_time,
+            grant.cliff,
+            grant.duration,
+        )?;
+
+        env.storage().persistent().set(
+            &AcademyDataKey::MilestoneAcceleration(grant_id),
+            &(acceleration, false),
+        );
+        if let Some(oracle) = milestone_oracle {
+            env.storage()
+                .persistent()
+                .set(&AcademyDataKey::MilestoneOracle(grant_id), &oracle);
+        }
+
+        Ok(grant_id)
+    }
+
+    /// Confirm a schedule's milestone (governance or its oracle).
+    ///
+    /// A gated schedule starts vesting now; an accelerating one has its end pulled in.
+    /// Returns when the milestone was met.
+    pub fn mark_milestone(env: Env, caller: Address, grant_id: u64) -> Result<u64, VestingError> {
+        caller.require_auth();
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        let is_governance = read_governance(&env) == Some(caller.clone());
+        let oracle: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&AcademyDataKey::MilestoneOracle(grant_id));
+        if !is_governance && oracle != Some(caller) {
+            return Err(VestingError::Unauthorized);
+        }
+
+        let met_at = env.ledger().timestamp();
+        let acceleration_key = AcademyDataKey::MilestoneAcceleration(grant_id);
+        let acceleration: Option<(u64, bool)> = env.storage().persistent().get(&acceleration_key);
+        if let Some((seconds, met)) = acceleration {
+            if met {
+                return Err(VestingError::MilestoneAlreadyMet);
+            }
+
+            // Claimed amounts are untouched; only the end moves earlier
+            schedule.duration = schedule
+                .duration
+                .saturating_sub(seconds)
+                .max(schedule.cliff);
+            assert_schedule_invariants(&schedule)?;
+            schedules.set(grant_id, schedule.clone());
+            persist_schedules(&env, &schedules);
+
+            env.storage()
+                .persistent()
+                .set(&acceleration_key, &(seconds, true));
+            env.storage()
+                .persistent()
+                .remove(&AcademyDataKey::MilestoneOracle(grant_id));
+            env.events().publish(
+                (symbol_short!("milestone"),),
+                (grant_id, met_at, schedule.duration),
+            );
+
+            return Ok(met_at);
+        }
+
+        if schedule.milestone_met {
+            return Err(VestingError::GrantNotPending);
+        }
+
+        schedule.start_time = met_at;
+        schedule.milestone_met = true;
+        schedule.pause_offset = paused_seconds(&env);
+        assert_schedule_invariants(&schedule)?;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        env.storage()
+            .persistent()
+            .remove(&AcademyDataKey::MilestoneOracle(grant_id));
+        env.events()
+            .publish((symbol_short!("milestone"),), (grant_id, met_at));
+
+        Ok(met_at)
+    }
+
+    /// Accept an acceptance-relative grant, starting its vesting clock now
+    pub fn accept_grant(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+    ) -> Result<u64, VestingError> {
+        beneficiary.require_auth();
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        if schedule.beneficiary != beneficiary {
+            return Err(VestingError::Unauthorized);
+        }
+        if !schedule.pending_acceptance {
+            return Err(VestingError::GrantNotPending);
+        }
+
+        let accepted_at = env.ledger().timestamp();
+        if accepted_at < read_min_start(&env) {
+            return Err(VestingError::StartTooEarly);
+        }
+
+        schedule.start_time = accepted_at;
+        schedule.pending_acceptance = false;
+        schedule.pause_offset = paused_seconds(&env);
+        assert_schedule_invariants(&schedule)?;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        env.events()
+            .publish((symbol_short!("accepted"),), (grant_id, accepted_at));
+
+        Ok(accepted_at)
+    }
+
+    /// Claim all currently vested, unclaimed tokens (atomic operation)
+    ///
+    /// Tokens are sent to the beneficiary's default claim destination if one is set.
+    /// Only the beneficiary authorizes `(grant_id, beneficiary)`, so a relayer may submit
+    /// the transaction and pay fees on their behalf.
+    pub fn claim(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+    ) -> Result<ClaimReceipt, VestingError> {
+        beneficiary.require_auth();
+
+        let payees = claim_payees(&env, grant_id, &beneficiary);
+        execute_claim(&env, grant_id, &beneficiary, &payees, false)
+    }
+
+    /// Claim a schedule flagged `requires_cosign`, authorized by beneficiary and governance
+    pub fn claim_cosigned(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+        governance: Address,
+    ) -> Result<ClaimReceipt, VestingError> {
+        beneficiary.require_auth();
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "claim_cosigned");
+
+        let payees = claim_payees(&env, grant_id, &beneficiary);
+        execute_claim(&env, grant_id, &beneficiary, &payees, true)
+    }
+
+    /// Claim vested tokens to an explicit destination, bypassing any default
+    pub fn claim_to(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+        destination: Address,
+    ) -> Result<ClaimReceipt, VestingError> {
+        beneficiary.require_auth();
+
+        if !destination_allowed(&env, &beneficiary, &destination) {
+            return Err(VestingError::DestinationNotAllowed);
+        }
+
+        let payees = sole_payee(&env, destination);
+        execute_claim(&env, grant_id, &beneficiary, &payees, false)
+    }
+
+    /// Claim vested tokens straight into a whitelisted staking contract.
+    ///
+    /// The staking contract's `stake(staker, amount)` is called on the beneficiary's
+    /// behalf; if it fails the whole claim is rolled back.
+    pub fn claim_and_stake(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+        staking_contract: Address,
+    ) -> Result<ClaimReceipt, VestingError> {
+        beneficiary.require_auth();
+
+        let allowed: bool = env
+            .storage()
+            .persistent()
+            .get(&AcademyDataKey::StakingContract(staking_contract.clone()))
+            .unwrap_or(false);
+        if !allowed {
+            return Err(VestingError::StakingNotAllowed);
+        }
+        if !destination_allowed(&env, &beneficiary, &staking_contract) {
+            return Err(VestingError::DestinationNotAllowed);
+        }
+
+        let payees = sole_payee(&env, staking_contract.clone());
+        let receipt = execute_claim(&env, grant_id, &beneficiary, &payees, false)?;
+
+        let args: Vec<Val> = soroban_sdk::vec![
+            &env,
+            beneficiary.into_val(&env),
+            (receipt.amount - receipt.fee).into_val(&env)
+        ];
+        let staked = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &staking_contract,
+            &symbol_short!("stake"),
+            args,
+        );
+        if !matches!(staked, Ok(Ok(_))) {
+            return Err(VestingError::StakeFailed);
+        }
+
+        env.events().publish(
+            (symbol_short!("staked"),),
+            (
+                grant_id,
+                beneficiary,
+                staking_contract,
+                receipt.amount - receipt.fee,
+            ),
+        );
+
+        Ok(receipt)
+    }
+
+    /// Approve or remove a staking contract for `claim_and_stake` (governance only)
+    pub fn set_staking_contract(
+        env: Env,
+        governance: Address,
+        staking_contract: Address,
+        allowed: bool,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_staking_contract");
+
+        let key = AcademyDataKey::StakingContract(staking_contract);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        Ok(())
+    }
+
+    /// Set the address that receives this beneficiary's claims by default
+    pub fn set_default_claim_destination(env: Env, beneficiary: Address, destination: Address) {
+        beneficiary.require_auth();
+
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::ClaimDestination(beneficiary), &destination);
+    }
+
+    /// Split this schedule's claims across destinations by bps summing to 10000 (beneficiary only).
+    ///
+    /// Applies to `claim` and `claim_cosigned`; an empty list removes the split.
+    pub fn set_claim_split(
+        env: Env,
+        grant_id: u64,
+        splits: Vec<(Address, u32)>,
+    ) -> Result<(), VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.beneficiary.require_auth();
+
+        let key = AcademyDataKey::ClaimSplit(grant_id);
+        if splits.is_empty() {
+            env.storage().persistent().remove(&key);
+            return Ok(());
+        }
+        if splits.len() > MAX_CLAIM_SPLITS {
+            return Err(VestingError::BatchTooLarge);
+        }
+        let mut total = 0u32;
+        for (payee, bps) in splits.iter() {
+            if bps == 0 {
+                return Err(VestingError::InvalidSplit);
+            }
+            if !destination_allowed(&env, &schedule.beneficiary, &payee) {
+                return Err(VestingError::DestinationNotAllowed);
+            }
+            total = total.saturating_add(bps);
+        }
+        if total != BPS_DENOMINATOR {
+            return Err(VestingError::InvalidSplit);
+        }
+
+        env.storage().persistent().set(&key, &splits);
+
+        Ok(())
+    }
+
+    /// Query a schedule's claim split, if any
+    pub fn get_claim_split(env: Env, grant_id: u64) -> Option<Vec<(Address, u32)>> {
+        env.storage()
+            .persistent()
+            .get(&AcademyDataKey::ClaimSplit(grant_id))
+    }
+
+    /// Remove the default claim destination so claims go to the beneficiary again
+    pub fn clear_default_claim_destination(env: Env, beneficiary: Address) {
+        beneficiary.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&AcademyDataKey::ClaimDestination(beneficiary));
+    }
+
+    /// Register (or clear with `None`) a contract told of this beneficiary's claims via `on_claim`
+    ///
+    /// Calls are best-effort: a failing notifier never blocks the claim.
+    pub fn set_personal_notifier(env: Env, beneficiary: Address, notifier: Option<Address>) {
+        beneficiary.require_auth();
+
+        let key = AcademyDataKey::PersonalNotifier(beneficiary);
+        match notifier {
+            Some(notifier) => env.storage().persistent().set(&key, &notifier),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Query a beneficiary's personal claim notifier, if any
+    pub fn get_personal_notifier(env: Env, beneficiary: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&AcademyDataKey::PersonalNotifier(beneficiary))
+    }
+
+    /// Restrict where a beneficiary's claims may go besides themselves (governance only).
+    ///
+    /// An empty list lifts the restriction. Defaults and splits pointing elsewhere fall back
+    /// to paying the beneficiary; `claim_to` fails with `DestinationNotAllowed`.
+    pub fn set_allowed_destinations(
+        env: Env,
+        governance: Address,
+        beneficiary: Address,
+        destinations: Vec<Address>,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_allowed_destinations");
+
+        if destinations.len() > MAX_ALLOWED_DESTINATIONS {
+            return Err(VestingError::BatchTooLarge);
+        }
+        let key = AcademyDataKey::AllowedDestinations(beneficiary.clone());
+        if destinations.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &destinations);
+        }
+        env.events()
+            .publish((symbol_short!("allow_dst"),), (beneficiary, destinations));
+
+        Ok(())
+    }
+
+    /// Query the destinations a beneficiary's claims are restricted to (empty = unrestricted)
+    pub fn get_allowed_destinations(env: Env, beneficiary: Address) -> Vec<Address> {
+        allowed_destinations(&env, &beneficiary)
+    }
+
+    /// Query a beneficiary's default claim destination, if any
+    pub fn get_default_claim_destination(env: Env, beneficiary: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&AcademyDataKey::ClaimDestination(beneficiary))
+    }
+
+    /// Claim multiple vested rewards atomically for a single beneficiary.
+    pub fn batch_claim(
+        env: Env,
+        grant_ids: Vec<u64>,
+        beneficiary: Address,
+    ) -> Result<i128, VestingError> {
+        beneficiary.require_auth();
+
+        if grant_ids.is_empty() {
+            return Ok(0);
+        }
+
+        if grant_ids.len() > MAX_BATCH_CLAIMS {
+            return Err(VestingError::BatchTooLarge);
+        }
+        // Each entry is priced from the pre-batch schedule, so a repeat would pay twice
+        if has_duplicate_ids(&env, &grant_ids) {
+            return Err(VestingError::DuplicateGrantId);
+        }
+
+        let mut schedules = load_schedules(&env)?;
+        let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        let current_balance = token_client.balance(&env.current_contract_address());
+        let current_time = env.ledger().timestamp();
+
+        let mut total_claimable = 0i128;
+        let mut updated_schedules = Vec::new(&env);
+
+        for grant_id in grant_ids.iter() {
+            let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+            if schedule.requires_cosign {
+                return Err(VestingError::CosignRequired);
+            }
+            let claim_amount = claimable_amount(&env, grant_id, &beneficiary, &schedule)?;
+
+            total_claimable += claim_amount;
+            record_claim(&env, grant_id, &mut schedule, claim_amount);
+            assert_schedule_invariants(&schedule)?;
+            updated_schedules.push_back((grant_id, schedule, claim_amount));
+        }
+
+        if current_balance < total_claimable {
+            return Err(diagnose(
+                &env,
+                VestingError::InsufficientBalance,
+                0,
+                total_claimable,
+                current_balance,
+            ));
+        }
+
+        let config = load_config(&env);
+        for (grant_id, schedule, claim_amount) in updated_schedules.iter() {
+            let payees = claim_payees(&env, grant_id, &beneficiary);
+            record_claim_ledger(&env, grant_id);
+            store_claimed_schedule(
+                &env,
+                &mut schedules,
+                grant_id,
+                schedule,
+                config.auto_finalize,
+            );
+            let fee = pay_claim_split(
+                &env,
+                &token_client,
+                grant_id,
+                &beneficiary,
+                &payees,
+                claim_amount,
+            )?;
+            mint_receipt(&env, grant_id, &beneficiary, claim_amount - fee);
+            notify_hooks(&env, HookCall::Claim, grant_id, &beneficiary, claim_amount);
+
+            // Split payouts already emitted one claim event per destination
+            if !config.summarize_batches && payees.len() == 1 {
+                env.events().publish(
+                    (symbol_short!("claim"),),
+                    ClaimEvent {
+                        grant_id,
+                        beneficiary: beneficiary.clone(),
+                        amount: claim_amount,
+                        fee,
+                        claimed_at: current_time,
+                    },
+                );
+
+                env.events().publish(
+                    (symbol_short!("v_claimed"),),
+                    VestingClaimed {
+                        grant_id,
+                        beneficiary: beneficiary.clone(),
+                        amount: claim_amount,
+                        claimed_at: current_time,
+                    },
+                );
+            }
+        }
+
+        if config.summarize_batches {
+            publish_batch_event(
+                &env,
+                symbol_short!("claim"),
+                updated_schedules.len(),
+                total_claimable,
+            );
+        }
+
+        persist_schedules(&env, &schedules);
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::LastClaim(beneficiary), &current_time);
+
+        Ok(total_claimable)
+    }
+
+    /// Push claimable tokens out to each schedule's beneficiary (governance only).
+    ///
+    /// Schedules with nothing claimable right now are skipped rather than failing the batch.
+    pub fn push_claims(
+        env: Env,
+        governance: Address,
+        grant_ids: Vec<u64>,
+    ) -> Result<i128, VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "push_claims");
+
+        if grant_ids.len() > MAX_BATCH_CLAIMS {
+            return Err(VestingError::BatchTooLarge);
+        }
+        // Each entry is priced from the pre-batch schedule, so a repeat would pay twice
+        if has_duplicate_ids(&env, &grant_ids) {
+            return Err(VestingError::DuplicateGrantId);
+        }
+
+        let mut schedules = load_schedules(&env)?;
+        let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        let current_balance = token_client.balance(&env.current_contract_address());
+        let current_time = env.ledger().timestamp();
+
+        let mut total_pushed = 0i128;
+        let mut pushes = Vec::new(&env);
+
+        for grant_id in grant_ids.iter() {
+            let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+            let beneficiary = schedule.beneficiary.clone();
+            let claim_amount = match claimable_amount(&env, grant_id, &beneficiary, &schedule) {
+                Ok(amount) => amount,
+                Err(_) => continue,
+            };
+
+            total_pushed += claim_amount;
+            record_claim(&env, grant_id, &mut schedule, claim_amount);
+            assert_schedule_invariants(&schedule)?;
+            pushes.push_back((grant_id, schedule, claim_amount));
+        }
+
+        if current_balance < total_pushed {
+            return Err(diagnose(
+                &env,
+                VestingError::InsufficientBalance,
+                0,
+                total_pushed,
+                current_balance,
+            ));
+        }
+
+        let config = load_config(&env);
+        for (grant_id, schedule, claim_amount) in pushes.iter() {
+            let beneficiary = schedule.beneficiary.clone();
+            let payees = claim_payees(&env, grant_id, &beneficiary);
+            record_claim_ledger(&env, grant_id);
+            store_claimed_schedule(
+                &env,
+                &mut schedules,
+                grant_id,
+                schedule,
+                config.auto_finalize,
+            );
+            let fee = pay_claim_split(
+                &env,
+                &token_client,
+                grant_id,
+                &beneficiary,
+                &payees,
+                claim_amount,
+            )?;
+            mint_receipt(&env, grant_id, &beneficiary, claim_amount - fee);
+            notify_hooks(&env, HookCall::Claim, grant_id, &beneficiary, claim_amount);
+            env.storage().persistent().set(
+                &AcademyDataKey::LastClaim(beneficiary.clone()),
+                &current_time,
+            );
+
+            // Split payouts already emitted one claim event per destination
+            if !config.summarize_batches && payees.len() == 1 {
+                env.events().publish(
+                    (symbol_short!("claim"),),
+                    ClaimEvent {
+                        grant_id,
+                        beneficiary: beneficiary.clone(),
+                        amount: claim_amount,
+                        fee,
+                        claimed_at: current_time,
+                    },
+                );
+
+                env.events().publish(
+                    (symbol_short!("v_claimed"),),
+                    VestingClaimed {
+                        grant_id,
+                        beneficiary,
+                        amount: claim_amount,
+                        claimed_at: current_time,
+                    },
+                );
+            }
+        }
+
+        if config.summarize_batches {
+            publish_batch_event(&env, symbol_short!("push"), pushes.len(), total_pushed);
+        }
+
+        persist_schedules(&env, &schedules);
+
+        Ok(total_pushed)
+    }
+
+    /// Reduce an unvested grant's amount, returning the difference to treasury (governance only).
+    ///
+    /// A partially funded schedule only returns funding above the new amount.
+    pub fn reduce_schedule(
+        env: Env,
+        governance: Address,
+        grant_id: u64,
+        new_amount: i128,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "reduce_schedule");
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        if schedule.revoked {
+            return Err(VestingError::Revoked);
+        }
+        if new_amount <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        if new_amount >= schedule.amount || new_amount < schedule.claimed_amount {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let config = load_config(&env);
+        let vested = Self::calculate_vested_amount(
+            &schedule,
+            vesting_clock(&env, &config, &schedule),
+            config.rounding_mode,
+        )?;
+        if vested > 0 || schedule.claimed_amount > 0 {
+            return Err(VestingError::VestingStarted);
+        }
+
+        let reclaimed = schedule.amount - new_amount;
+        // A partially funded schedule keeps what was paid in, up to its new amount
+        let funded = funded_amount(&env, grant_id);
+        let returned = match funded {
+            Some(funded) => (funded - new_amount).max(0),
+            None => reclaimed,
+        };
+        let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+        let treasury = read_treasury(&env).ok_or(VestingError::GovernanceNotSet)?;
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        if token_client.balance(&env.current_contract_address()) < returned {
+            return Err(VestingError::InsufficientBalance);
+        }
+
+        let old_amount = schedule.amount;
+        schedule.amount = new_amount;
+        assert_schedule_invariants(&schedule)?;
+        adjust_totals(&env, -reclaimed, 0, -reclaimed);
+        add_reclaimed(&env, returned);
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+        if let Some(funded) = funded {
+            env.storage().persistent().set(
+                &AcademyDataKey::FundedAmount(grant_id),
+                &(funded - returned),
+            );
+        }
+
+        if returned > 0 {
+            token_client.transfer(&env.current_contract_address(), &treasury, &returned);
+        }
+
+        env.events().publish(
+            (symbol_short!("reduce"),),
+            ReduceEvent {
+                grant_id,
+                old_amount,
+                new_amount,
+                reclaimed,
+                reduced_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Stretch or compress every active schedule's remaining time by `factor_bps` (governance only).
+    ///
+    /// 10000 leaves schedules unchanged; what has vested at call time is preserved. Returns
+    /// the number of schedules rescaled.
+    pub fn rescale_all(
+        env: Env,
+        governance: Address,
+        factor_bps: u32,
+    ) -> Result<u32, VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "rescale_all");
+
+        if factor_bps == 0 {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let config = load_config(&env);
+        let mut schedules = load_schedules(&env)?;
+        let mut count = 0u32;
+        let mut total_amount = 0i128;
+
+        for grant_id in active_ids(&env).iter() {
+            let Some(mut schedule) = schedules.get(grant_id) else {
+                continue;
+            };
+            let pivot = vesting_clock(&env, &config, &schedule);
+            let Some((start_time, cliff, duration)) =
+                rescaled_timeline(&schedule, pivot, factor_bps)?
+            else {
+                continue;
+            };
+
+            schedule.start_time = start_time;
+            schedule.cliff = cliff;
+            schedule.duration = duration;
+            assert_schedule_invariants(&schedule)?;
+            count += 1;
+            total_amount += schedule.amount;
+            schedules.set(grant_id, schedule);
+
+            if !config.summarize_batches {
+                env.events().publish(
+                    (symbol_short!("rescale"),),
+                    (grant_id, start_time, cliff, duration),
+                );
+            }
+        }
+
+        persist_schedules(&env, &schedules);
+        if config.summarize_batches {
+            publish_batch_event(&env, symbol_short!("rescale"), count, total_amount);
+        }
+
+        Ok(count)
+    }
+
+    /// Set the treasury that receives reclaimed tokens (governance only)
+    pub fn set_treasury(
+        env: Env,
+        governance: Address,
+        treasury: Address,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_treasury");
+
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::Treasury, &treasury);
+
+        Ok(())
+    }
+
+    /// Compare the contract's balance of `token` with what it still owes in that token.
+    ///
+    /// Returns `(balance, obligations, covered)`.
+    pub fn solvency_check(env: Env, token: Address) -> (i128, i128, bool) {
+        let balance =
+            soroban_sdk::token::Client::new(&env, &token).balance(&env.current_contract_address());
+        let obligations = if read_token(&env) == Some(token) {
+            outstanding_obligations(&env)
+        } else {
+            0
+        };
+
+        (balance, obligations, balance >= obligations)
+    }
+
+    /// Check whether the contract's balance covers a schedule's unclaimed obligation.
+    ///
+    /// Balances are pooled, so they are attributed to schedules in grant-ID order: a
+    /// schedule is funded once every older schedule's obligation has been covered first.
+    pub fn is_fully_funded(env: Env, grant_id: u64) -> Result<bool, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        let owed = unclaimed_obligation(&env, grant_id, &schedule);
+        if owed == 0 {
+            return Ok(true);
+        }
+
+        let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+        let balance =
+            soroban_sdk::token::Client::new(&env, &token).balance(&env.current_contract_address());
+        let mut senior = 0i128;
+        for (older_id, older) in schedules.iter() {
+            if older_id >= grant_id {
+                break;
+            }
+            senior += unclaimed_obligation(&env, older_id, &older);
+        }
+
+        Ok(balance - senior >= owed)
+    }
+
+    /// Set the earliest start_time new grants may use (governance only)
+    pub fn set_min_start(
+        env: Env,
+        governance: Address,
+        min_start: u64,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_min_start");
+
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::MinStart, &min_start);
+
+        Ok(())
+    }
+
+    /// Query the start date floor for new grants
+    pub fn get_min_start(env: Env) -> u64 {
+        read_min_start(&env)
+    }
+
+    /// Set the shortest duration new grants may use, 0 to allow any (governance only)
+    pub fn set_min_duration(
+        env: Env,
+        governance: Address,
+        seconds: u64,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_min_duration");
+
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::MinDuration, &seconds);
+
+        Ok(())
+    }
+
+    /// Query the duration floor for new grants
+    pub fn get_min_duration(env: Env) -> u64 {
+        read_min_duration(&env)
+    }
+
+    /// Configure (or clear with `None`) the receipt token minted on claims (governance only)
+    pub fn set_receipt_token(
+        env: Env,
+        governance: Address,
+        receipt_token: Option<Address>,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_receipt_token");
+
+        match receipt_token {
+            Some(receipt_token) => env
+                .storage()
+                .instance()
+                .set(&AcademyDataKey::ReceiptToken, &receipt_token),
+            None => env
+                .storage()
+                .instance()
+                .remove(&AcademyDataKey::ReceiptToken),
+        }
+
+        Ok(())
+    }
+
+    /// Append a hook contract to the ordered notification list (governance only)
+    pub fn add_hook(env: Env, governance: Address, hook: Address) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "add_hook");
+
+        let mut registered = hooks(&env);
+        if registered.contains(&hook) || registered.len() >= MAX_HOOKS {
+            return Err(VestingError::InvalidConfig);
+        }
+        registered.push_back(hook);
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::Hooks, &registered);
+
+        Ok(())
+    }
+
+    /// Remove a hook contract, keeping the order of the rest (governance only)
+    pub fn remove_hook(env: Env, governance: Address, hook: Address) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "remove_hook");
+
+        let mut registered = hooks(&env);
+        let index = registered
+            .first_index_of(&hook)
+            .ok_or(VestingError::InvalidConfig)?;
+        registered.remove(index);
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::Hooks, &registered);
+
+        Ok(())
+    }
+
+    /// Query the registered hook contracts in call order
+    pub fn list_hooks(env: Env) -> Vec<Address> {
+        hooks(&env)
+    }
+
+    /// Query the configured receipt token, if any
+    pub fn get_receipt_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&AcademyDataKey::ReceiptToken)
+    }
+
+    /// Decimals of the reward token (7 for deployments predating the record)
+    pub fn get_token_decimals(env: Env) -> u32 {
+        token_decimals(&env)
+    }
+
+    /// Pair a base-unit amount with the reward token's decimals for display
+    pub fn format_amount(env: Env, amount: i128) -> (i128, u32) {
+        (amount, token_decimals(&env))
+    }
+
+    /// Convert a whole-token amount into base units, e.g. for `min_claim` or `commitment_cap`
+    pub fn to_base_units(env: Env, whole_tokens: i128) -> Result<i128, VestingError> {
+        whole_tokens
+            .checked_mul(base_units_per_token(&env)?)
+            .ok_or(VestingError::InvalidConfig)
+    }
+
+    /// Convert base units into whole tokens, truncating any fractional part
+    pub fn to_whole_tokens(env: Env, base_units: i128) -> Result<i128, VestingError> {
+        Ok(base_units / base_units_per_token(&env)?)
+    }
+
+    /// Propose a new governance address (current governance only).
+    ///
+    /// The proposal can be accepted once `governance_timelock` seconds have passed.
+    pub fn propose_governance(
+        env: Env,
+        governance: Address,
+        new_governance: Address,
+    ) -> Result<u64, VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "propose_governance");
+
+        let accept_after = env
+            .ledger()
+            .timestamp()
+            .saturating_add(load_config(&env).governance_timelock);
+        let pending = PendingGovernance {
+            new_governance: new_governance.clone(),
+            accept_after,
+        };
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::PendingGovernance, &pending);
+
+        env.events().publish(
+            (symbol_short!("gov_prop"),),
+            (governance, new_governance, accept_after),
+        );
+
+        Ok(accept_after)
+    }
+
+    /// Accept a governance proposal after its timelock (proposed address only)
+    pub fn accept_governance(env: Env, new_governance: Address) -> Result<(), VestingError> {
+        new_governance.require_auth();
+
+        let pending: PendingGovernance = env
+            .storage()
+            .instance()
+            .get(&AcademyDataKey::PendingGovernance)
+            .ok_or(VestingError::NoPendingGovernance)?;
+
+        if pending.new_governance != new_governance {
+            return Err(VestingError::Unauthorized);
+        }
+        if env.ledger().timestamp() < pending.accept_after {
+            return Err(VestingError::GovernanceTimelockActive);
+        }
+
+        let previous = read_governance(&env).ok_or(VestingError::GovernanceNotSet)?;
+        log_admin_action(&env, &new_governance, "accept_governance");
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::Governance, &new_governance);
+        env.storage()
+            .instance()
+            .remove(&AcademyDataKey::PendingGovernance);
+
+        env.events()
+            .publish((symbol_short!("gov_acc"),), (previous, new_governance));
+
+        Ok(())
+    }
+
+    /// Query the outstanding governance proposal, if any
+    pub fn get_pending_governance(env: Env) -> Option<PendingGovernance> {
+        env.storage()
+            .instance()
+            .get(&AcademyDataKey::PendingGovernance)
+    }
+
+    /// Query the treasury address (governance unless explicitly set)
+    pub fn get_treasury(env: Env) -> Result<Address, VestingError> {
+        read_treasury(&env).ok_or(VestingError::GovernanceNotSet)
+    }
+
+    /// Move one schedule to a new beneficiary (current beneficiary only)
+    pub fn transfer_schedule(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+        new_beneficiary: Address,
+    ) -> Result<(), VestingError> {
+        Self::request_transfer(env, grant_id, beneficiary, new_beneficiary)?;
+
+        Ok(())
+    }
+
+    /// Request a schedule transfer, queued for `transfer_veto_window` seconds if one is set.
+    ///
+    /// Returns when the transfer takes effect (now when there is no veto window).
+    pub fn request_transfer(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+        new_beneficiary: Address,
+    ) -> Result<u64, VestingError> {
+        beneficiary.require_auth();
+
+        if transfers_paused(&env) {
+            return Err(VestingError::TransfersPaused);
+        }
+        if is_blocked(&env, &beneficiary) {
+            return Err(VestingError::BeneficiaryBlocked);
+        }
+        if new_beneficiary == env.current_contract_address() {
+            return Err(VestingError::InvalidBeneficiary);
+        }
+
+        let mut schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        if schedule.beneficiary != beneficiary {
+            return Err(VestingError::Unauthorized);
+        }
+        if !is_transferable(&schedule) {
+            return Err(VestingError::NotTransferable);
+        }
+        if has_pending_transfer(&env, grant_id) {
+            return Err(VestingError::TransferPending);
+        }
+        let Some(schedule) = settle_before_transfer(&env, &mut schedules, grant_id, schedule)?
+        else {
+            return Ok(env.ledger().timestamp());
+        };
+
+        let effective_at =
+            transfer_or_queue(&env, &mut schedules, grant_id, schedule, &new_beneficiary);
+        persist_schedules(&env, &schedules);
+
+        Ok(effective_at)
+    }
+
+    /// Complete a queued transfer once its veto window has passed (callable by anyone)
+    pub fn finalize_transfer(env: Env, grant_id: u64) -> Result<(), VestingError> {
+        if transfers_paused(&env) {
+            return Err(VestingError::TransfersPaused);
+        }
+
+        let key = AcademyDataKey::PendingTransfer(grant_id);
+        let pending: PendingTransfer = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::NoPendingTransfer)?;
+
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(VestingError::VetoWindowActive);
+        }
+
+        let mut schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        if !is_transferable(&schedule) {
+            return Err(VestingError::NotTransferable);
+        }
+        env.storage().persistent().remove(&key);
+
+        reassign_schedule(
+            &env,
+            &mut schedules,
+            grant_id,
+            schedule,
+            &pending.new_beneficiary,
+        );
+        persist_schedules(&env, &schedules);
+
+        Ok(())
+    }
+
+    /// Cancel a queued transfer during its veto window (governance only)
+    pub fn veto_transfer(env: Env, governance: Address, grant_id: u64) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "veto_transfer");
+
+        let key = AcademyDataKey::PendingTransfer(grant_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(VestingError::NoPendingTransfer);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("xfer_veto"),), (grant_id, governance));
+
+        Ok(())
+    }
+
+    /// Query the transfer queued for a schedule, if any
+    pub fn get_pending_transfer(env: Env, grant_id: u64) -> Option<PendingTransfer> {
+        env.storage()
+            .persistent()
+            .get(&AcademyDataKey::PendingTransfer(grant_id))
+    }
+
+    /// Swap the beneficiaries of two schedules in one step (governance only).
+    ///
+    /// Only for corrections before anything is claimed: either schedule having a claim fails
+    /// with `AlreadyClaimed`. Emits a `transfer` event for each side.
+    pub fn swap_beneficiaries(
+        env: Env,
+        governance: Address,
+        grant_a: u64,
+        grant_b: u64,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "swap_beneficiaries");
+
+        if grant_a == grant_b {
+            return Err(VestingError::InvalidSchedule);
+        }
+        let mut schedules = load_schedules(&env)?;
+        let schedule_a = schedules.get(grant_a).ok_or(VestingError::GrantNotFound)?;
+        let schedule_b = schedules.get(grant_b).ok_or(VestingError::GrantNotFound)?;
+        for (grant_id, schedule) in [(grant_a, &schedule_a), (grant_b, &schedule_b)] {
+            if schedule.revoked {
+                return Err(VestingError::Revoked);
+            }
+            if schedule.claimed || schedule.claimed_amount > 0 {
+                return Err(VestingError::AlreadyClaimed);
+            }
+            if has_pending_transfer(&env, grant_id) {
+                return Err(VestingError::TransferPending);
+            }
+        }
+
+        let beneficiary_a = schedule_a.beneficiary.clone();
+        let beneficiary_b = schedule_b.beneficiary.clone();
+        reassign_schedule(&env, &mut schedules, grant_a, schedule_a, &beneficiary_b);
+        reassign_schedule(&env, &mut schedules, grant_b, schedule_b, &beneficiary_a);
+        persist_schedules(&env, &schedules);
+
+        Ok(())
+    }
+
+    /// Move every transferable schedule of the caller to a new wallet, skipping the rest.
+    ///
+    /// Returns the IDs that were moved (or queued, under a veto window).
+    pub fn transfer_all(
+        env: Env,
+        beneficiary: Address,
+        new_beneficiary: Address,
+    ) -> Result<Vec<u64>, VestingError> {
+        beneficiary.require_auth();
+
+        if transfers_paused(&env) {
+            return Err(VestingError::TransfersPaused);
+        }
+        if is_blocked(&env, &beneficiary) {
+            return Err(VestingError::BeneficiaryBlocked);
+        }
+        if new_beneficiary == env.current_contract_address() {
+            return Err(VestingError::InvalidBeneficiary);
+        }
+
+        let mut schedules = load_schedules(&env)?;
+        let mut moved = Vec::new(&env);
+
+        for grant_id in user_schedule_ids(&env, &beneficiary).iter() {
+            let Some(schedule) = schedules.get(grant_id) else {
+                continue;
+            };
+            if !is_transferable(&schedule) || has_pending_transfer(&env, grant_id) {
+                continue;
+            }
+            let Some(schedule) = settle_before_transfer(&env, &mut schedules, grant_id, schedule)?
+            else {
+                continue;
+            };
+
+            transfer_or_queue(&env, &mut schedules, grant_id, schedule, &new_beneficiary);
+            moved.push_back(grant_id);
+        }
+
+        persist_schedules(&env, &schedules);
+
+        Ok(moved)
+    }
+
+    /// Cap a schedule's claims to `max_claim_per_period` per rolling `period_seconds` (governance only).
+    ///
+    /// A period opens with the first claim after the previous one lapses; 0/0 removes the cap.
+    pub fn set_period_cap(
+        env: Env,
+        governance: Address,
+        grant_id: u64,
+        max_claim_per_period: i128,
+        period_seconds: u64,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_period_cap");
+
+        let uncapped = max_claim_per_period == 0 && period_seconds == 0;
+        if !uncapped && (max_claim_per_period <= 0 || period_seconds == 0) {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.max_claim_per_period = max_claim_per_period;
+        schedule.period_seconds = period_seconds;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(())
+    }
+
+    /// Allow or forbid beneficiary transfers of a schedule (governance only)
+    pub fn set_transferable(
+        env: Env,
+        governance: Address,
+        grant_id: u64,
+        transferable: bool,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_transferable");
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.transferable = transferable;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(())
+    }
+
+    /// Require (or stop requiring) governance co-signature on claims of a schedule
+    pub fn set_requires_cosign(
+        env: Env,
+        governance: Address,
+        grant_id: u64,
+        requires_cosign: bool,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_requires_cosign");
+
+        let mut schedules = load_schedules(&env)?;
+        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        schedule.requires_cosign = requires_cosign;
+        schedules.set(grant_id, schedule);
+        persist_schedules(&env, &schedules);
+
+        Ok(())
+    }
+
+    /// Delete a fully claimed schedule and its index entry (beneficiary only)
+    pub fn finalize_schedule(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+    ) -> Result<(), VestingError> {
+        beneficiary.require_auth();
+
+        let mut schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        if schedule.beneficiary != beneficiary {
+            return Err(VestingError::Unauthorized);
+        }
+        if !schedule.claimed {
+            return Err(VestingError::NotVested);
+        }
+
+        remove_schedule(&env, &mut schedules, grant_id, &beneficiary);
+        persist_schedules(&env, &schedules);
+
+        Ok(())
+    }
+
+    /// Revoke a vesting schedule (governance/admin only, with timelock)
+    ///
+    /// Whatever had vested by the revoke stays claimable; once that is claimed (or if
+    /// nothing had vested), claims fail with `Revoked`.
+    pub fn revoke(
+        env: Env,
+        grant_id: u64,
+        admin: Address,
+        revoke_delay: u64,
+    ) -> Result<(), VestingError> {
+        admin.require_auth();
+
+        // Check pause state via CircuitBreaker
+        CircuitBreaker::require_not_paused(&env, symbol_short!("revoke"));
+
+        // Verify caller is admin
+        require_admin(&env, &admin, "revoke")?;
+        log_admin_action(&env, &admin, "revoke");
+
+        // Get vesting schedule
+        let schedules = load_schedules(&env)?;
+
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        // Cannot revoke already claimed
+        if schedule.claimed {
+            return Err(VestingError::AlreadyClaimed);
+        }
+
+        // Cannot revoke already revoked
+        if schedule.revoked {
+            return Err(VestingError::Revoked);
+        }
+
+        // Enforce timelock for revocation (minimum 1 hour)
+        if revoke_delay < 3600 {
+            return Err(VestingError::InvalidTimelock);
+        }
+
+        // A schedule that has not started has vested nothing: revoke it outright and
+        // return its whole amount to the treasury, leaving the beneficiary nothing
+        let current_time = env.ledger().timestamp();
+        let not_started = awaiting_start(&schedule) || current_time < schedule.start_time;
+        if !not_started && current_time < schedule.start_time.saturating_add(revoke_delay) {
+            // Check if enough time has passed since grant to allow revocation
+            return Err(VestingError::NotEnoughTimeForRevoke);
+        }
+
+        Self::revoke_unvested(&env, admin, grant_id, schedules, schedule, not_started)
+    }
+
+    /// Revoke the tranches of a stepped schedule that have not been reached yet (admin only).
+    ///
+    /// Every tranche up to the current one stays claimable and the value of the later ones
+    /// goes back to the treasury, as with `revoke` but without waiting out a timelock.
+    pub fn revoke_future_tranches(
+        env: Env,
+        grant_id: u64,
+        admin: Address,
+    ) -> Result<(), VestingError> {
+        admin.require_auth();
+
+        CircuitBreaker::require_not_paused(&env, symbol_short!("revoke"));
+
+        require_admin(&env, &admin, "revoke_future_tranches")?;
+        log_admin_action(&env, &admin, "revoke_future_tranches");
+
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        if schedule.claimed {
+            return Err(VestingError::AlreadyClaimed);
+        }
+        if schedule.revoked {
+            return Err(VestingError::Revoked);
+        }
+        if !matches!(schedule.curve, VestingCurve::Stepped(_)) {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        let not_started =
+            awaiting_start(&schedule) || env.ledger().timestamp() < schedule.start_time;
+        Self::revoke_unvested(&env, admin, grant_id, schedules, schedule, not_started)
+    }
+
+    /// Fully reverse a grant made within `grant_cancellation_window` (admin only).
+    ///
+    /// The whole amount goes back to the treasury and the schedule and its index entries
+    /// are deleted. Once the window has passed, or anything was claimed, only `revoke` remains.
+    pub fn cancel_grant(env: Env, admin: Address, grant_id: u64) -> Result<(), VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "cancel_grant")?;
+        log_admin_action(&env, &admin, "cancel_grant");
+
+        let mut schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        if schedule.revoked {
+            return Err(VestingError::Revoked);
+        }
+        if schedule.claimed_amount > 0 {
+            return Err(VestingError::VestingStarted);
+        }
+
+        let window = load_config(&env).grant_cancellation_window;
+        if env.ledger().timestamp() >= schedule.created_at.saturating_add(window) {
+            return Err(VestingError::CancellationWindowClosed);
+        }
+
+        let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+        let treasury = read_treasury(&env).ok_or(VestingError::GovernanceNotSet)?;
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        let returned = backed_refund(&env, grant_id, &schedule, schedule.amount);
+        if token_client.balance(&env.current_contract_address()) < returned {
+            return Err(VestingError::InsufficientBalance);
+        }
+
+        remove_schedule(&env, &mut schedules, grant_id, &schedule.beneficiary);
+        persist_schedules(&env, &schedules);
+        update_active(&env, grant_id, false);
 
-        // Emit grant event
-        let grant_event = GrantEvent {
-            grant_id: next_id,
-            beneficiary: beneficiary.clone(),
-            amount,
-            start_time,
-            cliff,
-            duration,
-            granted_at: current_timestamp,
-            granted_by: admin.clone(),
+        if returned > 0 {
+            token_client.transfer(&env.current_contract_address(), &treasury, &returned);
+        }
+        add_reclaimed(&env, returned);
+
+        env.events()
+            .publish((symbol_short!("cancel"),), (grant_id, returned));
+
+        Ok(())
+    }
+
+    /// Query vesting schedule details
+    pub fn get_vesting(env: Env, grant_id: u64) -> Result<VestingSchedule, VestingError> {
+        let schedules = load_schedules(&env)?;
+
+        schedules.get(grant_id).ok_or(VestingError::GrantNotFound)
+    }
+
+    /// Query a vesting schedule without trapping on unreadable storage.
+    ///
+    /// Returns `None` both for missing grants and for entries that fail to decode, so it
+    /// masks genuine corruption; use `get_vesting` when the distinction matters.
+    pub fn try_get_schedule(env: Env, grant_id: u64) -> Option<VestingSchedule> {
+        let raw: Val = env.storage().persistent().get(&symbol_short!("sched"))?;
+        let schedules = soroban_sdk::Map::<u64, Val>::try_from_val(&env, &raw).ok()?;
+        let value = schedules.try_get(grant_id).ok().flatten()?;
+        if !is_current_schedule(&env, &value) {
+            return None;
+        }
+
+        VestingSchedule::try_from_val(&env, &value).ok()
+    }
+
+    /// Report the roles `who` holds in this contract
+    pub fn caller_roles(env: Env, who: Address) -> Roles {
+        let is_admin = is_admin_address(&env, &who);
+
+        Roles {
+            is_admin,
+            is_governance: read_governance(&env) == Some(who.clone()),
+            is_granter: is_admin,
+            has_schedules: !user_schedule_ids(&env, &who).is_empty(),
+        }
+    }
+
+    /// Query the grant IDs indexed for a beneficiary
+    pub fn get_user_schedule_ids(env: Env, user: Address) -> Vec<u64> {
+        user_schedule_ids(&env, &user)
+    }
+
+    /// Load one page of a beneficiary's schedules, in index order.
+    ///
+    /// `limit` is clamped to `MAX_PAGE_SIZE`; pages past the end are empty.
+    pub fn get_user_schedules_paged(
+        env: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<VestingSchedule> {
+        let mut page = Vec::new(&env);
+        let ids = user_schedule_ids(&env, &user);
+        if start >= ids.len() {
+            return page;
+        }
+
+        let end = ids
+            .len()
+            .min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        for grant_id in ids.slice(start..end).iter() {
+            if let Some(schedule) = schedules.get(grant_id) {
+                page.push_back(schedule);
+            }
+        }
+
+        page
+    }
+
+    /// Extend storage TTLs for grant IDs `[cursor, cursor + limit)` so dormant schedules live on.
+    ///
+    /// Covers the schedule map, each schedule's own entries and its beneficiary's index.
+    /// `limit` is clamped to `MAX_PAGE_SIZE`. Returns the next cursor, or 0 once past the
+    /// last grant.
+    pub fn bump_all_ttls(env: Env, chunk_cursor: u64, limit: u32) -> u64 {
+        let counter = read_grant_counter(&env);
+        let first = chunk_cursor.max(1);
+        if first > counter {
+            return 0;
+        }
+
+        let storage = env.storage().persistent();
+        let schedules_key = symbol_short!("sched");
+        let Some(schedules) =
+            storage.get::<Symbol, soroban_sdk::Map<u64, VestingSchedule>>(&schedules_key)
+        else {
+            return 0;
+        };
+        storage.extend_ttl(&schedules_key, TTL_BUMP_THRESHOLD, TTL_BUMP_EXTEND_TO);
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_BUMP_THRESHOLD, TTL_BUMP_EXTEND_TO);
+
+        let end = counter.min(first.saturating_add(u64::from(limit.min(MAX_PAGE_SIZE))) - 1);
+        for grant_id in first..=end {
+            let issued_key = AcademyDataKey::IssuedId(grant_id);
+            if storage.has(&issued_key) {
+                storage.extend_ttl(&issued_key, TTL_BUMP_THRESHOLD, TTL_BUMP_EXTEND_TO);
+            }
+            let Some(schedule) = schedules.get(grant_id) else {
+                continue;
+            };
+            let user_key = AcademyDataKey::UserScheduleIds(schedule.beneficiary);
+            if storage.has(&user_key) {
+                storage.extend_ttl(&user_key, TTL_BUMP_THRESHOLD, TTL_BUMP_EXTEND_TO);
+            }
+            for key in grant_side_keys(grant_id).iter() {
+                if storage.has(key) {
+                    storage.extend_ttl(key, TTL_BUMP_THRESHOLD, TTL_BUMP_EXTEND_TO);
+                }
+            }
+        }
+
+        if end >= counter {
+            0
+        } else {
+            end + 1
+        }
+    }
+
+    /// IDs in `[start, start + limit)` that still have a stored schedule, terminal ones included.
+    ///
+    /// `limit` is clamped to `MAX_PAGE_SIZE` and counts IDs walked, not IDs returned, so
+    /// deleted schedules leave pages short; continue from `start + limit`.
+    pub fn get_all_schedule_ids_paged(env: Env, start: u64, limit: u32) -> Vec<u64> {
+        let mut page = Vec::new(&env);
+        let first = start.max(1);
+        let end = read_grant_counter(&env)
+            .min(first.saturating_add(u64::from(limit.min(MAX_PAGE_SIZE))) - 1);
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        for grant_id in first..=end {
+            if schedules.contains_key(grant_id) {
+                page.push_back(grant_id);
+            }
+        }
+
+        page
+    }
+
+    /// IDs of schedules granted within `[from, to]` (inclusive), in grant order
+    pub fn schedules_created_between(env: Env, from: u64, to: u64) -> Vec<u64> {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        let mut matching = Vec::new(&env);
+        for (grant_id, schedule) in schedules.iter() {
+            if (from..=to).contains(&schedule.created_at) {
+                matching.push_back(grant_id);
+            }
+        }
+
+        matching
+    }
+
+    /// Query every token grants have been made in, in first-use order
+    pub fn get_used_tokens(env: Env) -> Vec<Address> {
+        used_tokens(&env)
+    }
+
+    /// Live SEP-41 allowances this contract has granted, as `(token, spender, amount)`.
+    ///
+    /// The contract approves nothing itself, so this should stay empty. Every used token
+    /// is checked against the contracts it calls: registered hooks and the receipt token.
+    pub fn get_outstanding_approvals(env: Env) -> Vec<(Address, Address, i128)> {
+        let mut spenders = hooks(&env);
+        let receipt_token: Option<Address> =
+            env.storage().instance().get(&AcademyDataKey::ReceiptToken);
+        if let Some(receipt_token) = receipt_token {
+            spenders.push_back(receipt_token);
+        }
+
+        let owner = env.current_contract_address();
+        let mut approvals = Vec::new(&env);
+        for token in used_tokens(&env).iter() {
+            let token_client = soroban_sdk::token::Client::new(&env, &token);
+            for spender in spenders.iter() {
+                let amount = token_client.allowance(&owner, &spender);
+                if amount > 0 {
+                    approvals.push_back((token.clone(), spender, amount));
+                }
+            }
+        }
+
+        approvals
+    }
+
+    /// Query a schedule's lifecycle stage
+    pub fn get_schedule_status(env: Env, grant_id: u64) -> Result<ScheduleStatus, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        Ok(schedule_status(&env, &load_config(&env), &schedule))
+    }
+
+    /// Emit a `("status",)` event `(grant_id, from, to)` for each schedule whose status moved
+    /// since it was last ticked (callable by anyone).
+    ///
+    /// Untouched schedules count as `Pending`; a schedule that skipped stages between ticks gets
+    /// one event for the whole jump. Unknown IDs are skipped and at most `MAX_PAGE_SIZE` IDs
+    /// are processed. Returns how many transitions were emitted.
+    pub fn tick(env: Env, grant_ids: Vec<u64>) -> u32 {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let config = load_config(&env);
+
+        let mut transitions = 0u32;
+        for grant_id in grant_ids.iter().take(MAX_PAGE_SIZE as usize) {
+            let Some(schedule) = schedules.get(grant_id) else {
+                continue;
+            };
+            let key = AcademyDataKey::LastStatus(grant_id);
+            let from: ScheduleStatus = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(ScheduleStatus::Pending);
+            let to = schedule_status(&env, &config, &schedule);
+            if from == to {
+                continue;
+            }
+
+            env.storage().persistent().set(&key, &to);
+            env.events()
+                .publish((symbol_short!("status"),), (grant_id, from, to));
+            transitions += 1;
+        }
+
+        transitions
+    }
+
+    /// IDs of schedules currently in `status`.
+    ///
+    /// Live statuses scan the active index; `Claimed` and `Revoked` scan all stored schedules.
+    /// Either way at most `MAX_STATUS_SCAN` entries are inspected, so cost is bounded.
+    pub fn get_schedules_by_status(env: Env, status: ScheduleStatus) -> Vec<u64> {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let candidates = match status {
+            ScheduleStatus::Claimed | ScheduleStatus::Revoked => schedules.keys(),
+            _ => active_ids(&env),
+        };
+
+        let config = load_config(&env);
+        let mut matching = Vec::new(&env);
+        for grant_id in candidates.iter().take(MAX_STATUS_SCAN as usize) {
+            if let Some(schedule) = schedules.get(grant_id) {
+                if schedule_status(&env, &config, &schedule) == status {
+                    matching.push_back(grant_id);
+                }
+            }
+        }
+
+        matching
+    }
+
+    /// The most recent `limit` privileged actions, oldest first
+    pub fn get_admin_log(env: Env, limit: u32) -> Vec<AdminLogEntry> {
+        let log = admin_log(&env);
+        let start = log.len().saturating_sub(limit);
+
+        log.slice(start..)
+    }
+
+    /// Query the IDs of schedules neither fully claimed nor revoked, in grant order
+    pub fn get_active_schedule_ids(env: Env) -> Vec<u64> {
+        active_ids(&env)
+    }
+
+    /// Drop active-index entries whose schedule is gone or terminal (admin only).
+    ///
+    /// Only prunes; surviving IDs keep their order. Returns the number pruned.
+    pub fn compact_active_index(env: Env, admin: Address) -> Result<u32, VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "compact_active_index")?;
+        log_admin_action(&env, &admin, "compact_active_index");
+
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        let ids = active_ids(&env);
+        let mut kept = Vec::new(&env);
+        for grant_id in ids.iter() {
+            let live = schedules
+                .get(grant_id)
+                .is_some_and(|schedule| !schedule.claimed && !schedule.revoked);
+            if live {
+                kept.push_back(grant_id);
+            }
+        }
+
+        let pruned = ids.len() - kept.len();
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::ActiveIds, &kept);
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::ActiveCount, &(kept.len() as u64));
+
+        Ok(pruned)
+    }
+
+    /// Query the running `(committed, claimed, outstanding)` totals in base units.
+    ///
+    /// Outstanding excludes revoked schedules. Deployments predating the totals read
+    /// zero until `recompute_totals` is run.
+    pub fn get_totals(env: Env) -> (i128, i128, i128) {
+        totals(&env)
+    }
+
+    /// Vested-but-unclaimed base units across the active index at the current time.
+    ///
+    /// Revoked schedules leave the active index, so amounts they retained are not counted.
+    pub fn total_accrued_liability(env: Env) -> i128 {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let config = load_config(&env);
+
+        let mut liability = 0i128;
+        for grant_id in active_ids(&env).iter() {
+            if let Some(schedule) = schedules.get(grant_id) {
+                let accrued = unclaimed_vested(&env, &config, grant_id, &schedule).unwrap_or(0);
+                liability += accrued.max(0);
+            }
+        }
+
+        liability
+    }
+
+    /// Active grant IDs with the most unclaimed `amount - claimed_amount`, largest first.
+    ///
+    /// `n` is clamped to `MAX_TOP_SCHEDULES`; ties keep the lower grant ID first.
+    pub fn top_schedules_by_outstanding(env: Env, n: u32) -> Vec<u64> {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let n = n.min(MAX_TOP_SCHEDULES);
+        if n == 0 {
+            return Vec::new(&env);
+        }
+
+        // Kept sorted descending and never longer than n
+        let mut top: Vec<(i128, u64)> = Vec::new(&env);
+        for grant_id in active_ids(&env).iter() {
+            let Some(schedule) = schedules.get(grant_id) else {
+                continue;
+            };
+            let outstanding = schedule.amount - schedule.claimed_amount;
+            if top.len() == n && outstanding <= top.get_unchecked(n - 1).0 {
+                continue;
+            }
+
+            let mut at = top.len();
+            while at > 0 && top.get_unchecked(at - 1).0 < outstanding {
+                at -= 1;
+            }
+            top.insert(at, (outstanding, grant_id));
+            if top.len() > n {
+                top.pop_back();
+            }
+        }
+
+        let mut ids = Vec::new(&env);
+        for (_, grant_id) in top.iter() {
+            ids.push_back(grant_id);
+        }
+        ids
+    }
+
+    /// Query the base units revoke, reduce and cancel have returned to the treasury.
+    ///
+    /// Only tokens that actually left the contract count, so the unfunded part of a partially
+    /// funded schedule is written off without being reclaimed.
+    pub fn get_total_reclaimed(env: Env) -> i128 {
+        total_reclaimed(&env)
+    }
+
+    /// Rebuild the running totals from every stored schedule (admin only).
+    ///
+    /// Scans every stored schedule and overwrites the stored totals with the
+    /// result, which is also returned as `(committed, claimed, outstanding)`.
+    pub fn recompute_totals(env: Env, admin: Address) -> Result<(i128, i128, i128), VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "recompute_totals")?;
+        log_admin_action(&env, &admin, "recompute_totals");
+
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        let (mut committed, mut claimed, mut outstanding) = (0i128, 0i128, 0i128);
+        for (grant_id, schedule) in schedules.iter() {
+            committed += schedule.amount;
+            claimed += schedule.claimed_amount;
+            outstanding += unclaimed_obligation(&env, grant_id, &schedule);
+        }
+
+        let recomputed = (committed, claimed, outstanding);
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::Totals, &recomputed);
+        env.events().publish((symbol_short!("totals"),), recomputed);
+
+        Ok(recomputed)
+    }
+
+    /// Query how many schedules are neither fully claimed nor revoked
+    pub fn active_schedule_count(env: Env) -> u64 {
+        active_count(&env)
+    }
+
+    /// Query how many distinct addresses have ever held a schedule
+    pub fn get_beneficiary_count(env: Env) -> u64 {
+        beneficiary_count(&env)
+    }
+
+    /// Report a beneficiary's indexed grant IDs and which of them lack a stored schedule
+    pub fn debug_user_keys(env: Env, user: Address) -> UserKeysReport {
+        let indexed = user_schedule_ids(&env, &user);
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        let mut missing = Vec::new(&env);
+        for grant_id in indexed.iter() {
+            if !schedules.contains_key(grant_id) {
+                missing.push_back(grant_id);
+            }
+        }
+
+        UserKeysReport { indexed, missing }
+    }
+
+    /// Cross-check the active and per-user indexes against stored schedules.
+    ///
+    /// Revoked schedules have no index of their own; they are checked for absence from the
+    /// active index. User indexes are reached through the beneficiaries of stored schedules.
+    pub fn audit_indexes(env: Env) -> IndexAuditReport {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let active = active_ids(&env);
+
+        let mut report = IndexAuditReport {
+            scanned: u64::from(schedules.len()),
+            active_orphaned: 0,
+            active_missing: 0,
+            active_count_drift: active_count(&env) != u64::from(active.len()),
+            user_orphaned: 0,
+            user_missing: 0,
+        };
+
+        for grant_id in active.iter() {
+            let live = schedules
+                .get(grant_id)
+                .is_some_and(|schedule| !schedule.revoked && !schedule.claimed);
+            if !live {
+                report.active_orphaned += 1;
+            }
+        }
+
+        let mut beneficiaries: Vec<Address> = Vec::new(&env);
+        for (grant_id, schedule) in schedules.iter() {
+            if !schedule.revoked && !schedule.claimed && !active.contains(grant_id) {
+                report.active_missing += 1;
+            }
+            if !user_schedule_ids(&env, &schedule.beneficiary).contains(grant_id) {
+                report.user_missing += 1;
+            }
+            if !beneficiaries.contains(&schedule.beneficiary) {
+                beneficiaries.push_back(schedule.beneficiary);
+            }
+        }
+
+        for user in beneficiaries.iter() {
+            for grant_id in user_schedule_ids(&env, &user).iter() {
+                let owned = schedules
+                    .get(grant_id)
+                    .is_some_and(|schedule| schedule.beneficiary == user);
+                if !owned {
+                    report.user_orphaned += 1;
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Explain whether the beneficiary could claim a schedule now, and if not, why
+    pub fn claim_eligibility(env: Env, grant_id: u64) -> Result<ClaimEligibility, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        let config = load_config(&env);
+        let now = env.ledger().timestamp();
+
+        let status = schedule_status(&env, &config, &schedule);
+        let mut claimable = unclaimed_vested(&env, &config, grant_id, &schedule)?;
+        if schedule.period_seconds > 0 {
+            claimable = claimable.min(period_allowance(&env, grant_id, &schedule));
+        }
+
+        let last_claim: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&AcademyDataKey::LastClaim(schedule.beneficiary.clone()));
+        let last_ledger: Option<(u32, u64)> = env
+            .storage()
+            .persistent()
+            .get(&AcademyDataKey::LastClaimLedger(grant_id));
+        let cooling_down = config.default_cooldown > 0
+            && last_claim.is_some_and(|last| now < last + config.default_cooldown);
+
+        Ok(ClaimEligibility {
+            eligible: claimable_amount(&env, grant_id, &schedule.beneficiary, &schedule).is_ok(),
+            in_cliff: matches!(status, ScheduleStatus::Pending | ScheduleStatus::Cliff),
+            paused: env.storage().instance().has(&symbol_short!("paused_at")),
+            blocked: is_blocked(&env, &schedule.beneficiary),
+            cooldown_active: cooling_down || last_ledger == Some(current_ledger(&env)),
+            below_minimum: claimable > 0
+                && claimable < config.min_claim
+                && !is_final_sweep(&env, grant_id, &schedule, claimable),
+            nothing_claimable: claimable <= 0,
+        })
+    }
+
+    /// Query a schedule's most recent claims, oldest first
+    pub fn get_claim_history(env: Env, grant_id: u64) -> Vec<ClaimRecord> {
+        claim_history(&env, grant_id)
+    }
+
+    /// Bundle a schedule's parameters, claim history, revoke snapshot and current state
+    pub fn schedule_replay(env: Env, grant_id: u64) -> Result<ScheduleReplay, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        let config = load_config(&env);
+
+        let vested_now = if schedule.revoked {
+            unclaimed_obligation(&env, grant_id, &schedule) + schedule.claimed_amount
+        } else {
+            Self::calculate_vested_amount(
+                &schedule,
+                vesting_clock(&env, &config, &schedule),
+                config.rounding_mode,
+            )?
+        };
+
+        Ok(ScheduleReplay {
+            grant_id,
+            status: schedule_status(&env, &config, &schedule),
+            claims: claim_history(&env, grant_id),
+            vested_at_revoke: env
+                .storage()
+                .persistent()
+                .get(&AcademyDataKey::VestedAtRevoke(grant_id))
+                .unwrap_or(0),
+            vested_now,
+            schedule,
+        })
+    }
+
+    /// Calculate vested amount at current time
+    pub fn get_vested_amount(env: Env, grant_id: u64) -> Result<i128, VestingError> {
+        let schedules = load_schedules(&env)?;
+
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        let config = load_config(&env);
+        let clock = vesting_clock(&env, &config, &schedule);
+        Self::calculate_vested_amount(&schedule, clock, config.rounding_mode)
+    }
+
+    /// Preview a claim without executing it: `(amount claimed now, amount left afterward)`.
+    ///
+    /// The second value matches `ClaimReceipt::remaining`, so it is 0 for a completing claim.
+    pub fn simulate_claim(env: Env, grant_id: u64) -> Result<(i128, i128), VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        let claim = match claimable_amount(&env, grant_id, &schedule.beneficiary, &schedule) {
+            Ok(amount) => amount,
+            Err(VestingError::NotVested) => 0,
+            Err(error) => return Err(error),
         };
 
-        env.events().publish((symbol_short!("grant"),), grant_event);
+        Ok((claim, schedule.amount - schedule.claimed_amount - claim))
+    }
+
+    /// Summed vested amount across a user's schedules at each sampled timestamp.
+    ///
+    /// Revoked schedules are left out. At most `MAX_CURVE_SAMPLES` samples per call.
+    pub fn aggregate_curve(
+        env: Env,
+        user: Address,
+        sample_timestamps: Vec<u64>,
+    ) -> Result<Vec<i128>, VestingError> {
+        if sample_timestamps.len() > MAX_CURVE_SAMPLES {
+            return Err(VestingError::BatchTooLarge);
+        }
+
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let mut user_schedules = Vec::new(&env);
+        for grant_id in user_schedule_ids(&env, &user).iter() {
+            if let Some(schedule) = schedules.get(grant_id) {
+                if !schedule.revoked {
+                    user_schedules.push_back(schedule);
+                }
+            }
+        }
+
+        let rounding = load_config(&env).rounding_mode;
+        let mut curve = Vec::new(&env);
+        for timestamp in sample_timestamps.iter() {
+            let mut total = 0i128;
+            for schedule in user_schedules.iter() {
+                total += Self::calculate_vested_amount(&schedule, timestamp, rounding)?;
+            }
+            curve.push_back(total);
+        }
+
+        Ok(curve)
+    }
+
+    /// Pause the whole contract (admin only), starting the paused-time clock
+    pub fn pause(env: Env, admin: Address) {
+        CircuitBreaker::set_pause_level(&env, admin.clone(), PauseLevel::Full);
+        log_admin_action(&env, &admin, "pause");
+
+        let paused_at_key = symbol_short!("paused_at");
+        if !env.storage().instance().has(&paused_at_key) {
+            env.storage()
+                .instance()
+                .set(&paused_at_key, &env.ledger().timestamp());
+        }
+    }
+
+    /// Unpause the contract (admin only), folding the pause into cumulative paused time
+    pub fn unpause(env: Env, admin: Address) {
+        CircuitBreaker::set_pause_level(&env, admin.clone(), PauseLevel::None);
+        log_admin_action(&env, &admin, "unpause");
+
+        let paused_at_key = symbol_short!("paused_at");
+        if env.storage().instance().has(&paused_at_key) {
+            let total = paused_seconds(&env);
+            env.storage().instance().remove(&paused_at_key);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("paused_s"), &total);
+        }
+    }
+
+    /// Query cumulative seconds spent paused, including a pause in progress
+    pub fn get_paused_seconds(env: Env) -> u64 {
+        paused_seconds(&env)
+    }
+
+    /// Pause or resume schedule transfers only, leaving grants and claims running (admin only)
+    pub fn set_transfers_paused(
+        env: Env,
+        admin: Address,
+        paused: bool,
+    ) -> Result<(), VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "set_transfers_paused")?;
+        log_admin_action(&env, &admin, "set_transfers_paused");
+
+        if paused {
+            env.storage()
+                .instance()
+                .set(&AcademyDataKey::TransfersPaused, &true);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&AcademyDataKey::TransfersPaused);
+        }
+        env.events()
+            .publish((symbol_short!("xfer_hold"),), (admin, paused));
+
+        Ok(())
+    }
+
+    /// Replace the single admin with an M-of-N signer set, or clear it with an empty list.
+    ///
+    /// Changing the set is itself privileged, so it needs the current threshold when a set
+    /// exists. Pause and unpause stay with the circuit breaker's admin role.
+    pub fn set_admin_signers(
+        env: Env,
+        admin: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "set_admin_signers")?;
+        log_admin_action(&env, &admin, "set_admin_signers");
 
-        // Emit CredentialIssued event (for indexer compatibility)
-        let credential_event = CredentialIssued {
-            grant_id: next_id,
-            beneficiary,
-            amount,
-            start_time,
-            cliff,
-            duration,
-            granted_at: current_timestamp,
-            granted_by: admin,
-        };
+        if signers.is_empty() {
+            env.storage()
+                .instance()
+                .remove(&AcademyDataKey::AdminSigners);
+            env.events().publish(
+                (symbol_short!("signers"),),
+                (Vec::<Address>::new(&env), 0u32),
+            );
+            return Ok(());
+        }
+        if signers.len() > MAX_ADMIN_SIGNERS || threshold == 0 || threshold > signers.len() {
+            return Err(VestingError::InvalidConfig);
+        }
+        for (index, signer) in signers.iter().enumerate() {
+            if signers.first_index_of(&signer) != Some(index as u32) {
+                return Err(VestingError::InvalidConfig);
+            }
+        }
 
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::AdminSigners, &(signers.clone(), threshold));
         env.events()
-            .publish((symbol_short!("cred_iss"),), credential_event);
+            .publish((symbol_short!("signers"),), (signers, threshold));
 
-        Ok(next_id)
+        Ok(())
     }
 
-    /// Claim vested tokens (atomic operation, single-claim semantics)
-    pub fn claim(env: Env, grant_id: u64, beneficiary: Address) -> Result<i128, VestingError> {
-        beneficiary.require_auth();
+    /// Approve `executor`'s next call of the admin action `action` as a co-signer (signer only).
+    ///
+    /// `action` is the name the call records in the admin log. The signer must also
+    /// authorize that call itself, so the approval only counts for the invocation it signs.
+    pub fn approve_admin_call(
+        env: Env,
+        signer: Address,
+        executor: Address,
+        action: Symbol,
+    ) -> Result<(), VestingError> {
+        signer.require_auth();
 
-        let mut schedules = load_schedules(&env)?;
-        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
-        let vested_amount = claimable_amount(&env, grant_id, &beneficiary, &schedule)?;
+        let (signers, _) = admin_signers(&env).ok_or(VestingError::Unauthorized)?;
+        if signer == executor || !signers.contains(&signer) || !signers.contains(&executor) {
+            return Err(VestingError::Unauthorized);
+        }
 
-        // Verify contract has sufficient balance
-        let token_key = symbol_short!("token");
-        let token: Address = env
+        let key = AcademyDataKey::AdminApprovals(executor.clone(), action.clone());
+        let mut approvals: Vec<Address> = env
             .storage()
             .persistent()
-            .get(&token_key)
-            .ok_or(VestingError::Unauthorized)?;
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !approvals.contains(&signer) {
+            approvals.push_back(signer.clone());
+            env.storage().persistent().set(&key, &approvals);
+        }
+        env.events()
+            .publish((symbol_short!("approve"),), (signer, executor, action));
 
-        let token_client = soroban_sdk::token::Client::new(&env, &token);
-        let balance = token_client.balance(&env.current_contract_address());
+        Ok(())
+    }
 
-        if balance < vested_amount {
-            return Err(VestingError::InsufficientBalance);
-        }
+    /// Query the admin signer set and threshold, if one replaces the single admin
+    pub fn get_admin_signers(env: Env) -> Option<(Vec<Address>, u32)> {
+        admin_signers(&env)
+    }
 
-        // Mark as claimed (atomic operation)
-        schedule.claimed = true;
-        schedules.set(grant_id, schedule.clone());
-        persist_schedules(&env, &schedules);
+    /// Check whether schedule transfers are paused
+    pub fn transfers_paused(env: Env) -> bool {
+        transfers_paused(&env)
+    }
 
-        // Transfer tokens
-        token_client.transfer(
-            &env.current_contract_address(),
-            &beneficiary,
-            &vested_amount,
-        );
+    /// Block an address from claiming (governance only); vesting keeps accruing
+    pub fn block_address(env: Env, governance: Address, user: Address) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "block_address");
 
-        let current_time = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&AcademyDataKey::Blocked(user.clone()), &true);
+        env.events().publish((symbol_short!("blocked"),), user);
 
-        // Emit claim event
-        let claim_event = ClaimEvent {
-            grant_id,
-            beneficiary: beneficiary.clone(),
-            amount: vested_amount,
-            claimed_at: current_time,
-        };
+        Ok(())
+    }
 
-        env.events().publish((symbol_short!("claim"),), claim_event);
+    /// Lift a claim block from an address (governance only)
+    pub fn unblock_address(
+        env: Env,
+        governance: Address,
+        user: Address,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "unblock_address");
 
-        // Emit VestingClaimed event (for indexer)
-        let vesting_claimed = VestingClaimed {
-            grant_id,
-            beneficiary,
-            amount: vested_amount,
-            claimed_at: current_time,
-        };
+        env.storage()
+            .persistent()
+            .remove(&AcademyDataKey::Blocked(user.clone()));
+        env.events().publish((symbol_short!("unblocked"),), user);
 
-        env.events()
-            .publish((symbol_short!("v_claimed"),), vesting_claimed);
+        Ok(())
+    }
 
-        Ok(vested_amount)
+    /// Check whether an address is blocked from claiming
+    pub fn is_blocked(env: Env, user: Address) -> bool {
+        is_blocked(&env, &user)
     }
 
-    /// Claim multiple vested rewards atomically for a single beneficiary.
-    pub fn batch_claim(
+    /// Atomically replace all governance-tunable parameters
+    pub fn set_config(
         env: Env,
-        grant_ids: Vec<u64>,
-        beneficiary: Address,
-    ) -> Result<i128, VestingError> {
-        beneficiary.require_auth();
-
-        if grant_ids.is_empty() {
-            return Ok(0);
-        }
+        governance: Address,
+        config: GlobalConfig,
+    ) -> Result<(), VestingError> {
+        require_governance(&env, &governance)?;
+        log_admin_action(&env, &governance, "set_config");
 
-        if grant_ids.len() > MAX_BATCH_CLAIMS {
-            return Err(VestingError::BatchTooLarge);
+        if config.min_claim < 0
+            || config.commitment_cap < 0
+            || config.default_cliff_bps > BPS_DENOMINATOR
+            || config.claim_fee_bps > BPS_DENOMINATOR
+        {
+            return Err(VestingError::InvalidConfig);
         }
 
-        let mut schedules = load_schedules(&env)?;
-        let token: Address = env
-            .storage()
+        env.storage()
             .persistent()
-            .get(&symbol_short!("token"))
-            .ok_or(VestingError::Unauthorized)?;
-        let token_client = soroban_sdk::token::Client::new(&env, &token);
-        let current_balance = token_client.balance(&env.current_contract_address());
-        let current_time = env.ledger().timestamp();
-
-        let mut total_claimable = 0i128;
-        let mut updated_schedules = Vec::new(&env);
-
-        for grant_id in grant_ids.iter() {
-            let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
-            let claim_amount = claimable_amount(&env, grant_id, &beneficiary, &schedule)?;
+            .set(&symbol_short!("config"), &config);
 
-            total_claimable += claim_amount;
-            schedule.claimed = true;
-            updated_schedules.push_back((grant_id, schedule, claim_amount));
-        }
+        env.events().publish((symbol_short!("config"),), config);
 
-        if current_balance < total_claimable {
-            return Err(VestingError::InsufficientBalance);
-        }
+        Ok(())
+    }
 
-        for (grant_id, schedule, claim_amount) in updated_schedules.iter() {
-            schedules.set(grant_id, schedule);
-            token_client.transfer(&env.current_contract_address(), &beneficiary, &claim_amount);
+    /// Query the current governance-tunable parameters
+    pub fn get_config(env: Env) -> GlobalConfig {
+        load_config(&env)
+    }
 
-            env.events().publish(
-                (symbol_short!("claim"),),
-                ClaimEvent {
-                    grant_id,
-                    beneficiary: beneficiary.clone(),
-                    amount: claim_amount,
-                    claimed_at: current_time,
-                },
-            );
+    /// Query the tunable numeric parameters only, without flags or addresses
+    pub fn get_parameters(env: Env) -> Parameters {
+        let config = load_config(&env);
 
-            env.events().publish(
-                (symbol_short!("v_claimed"),),
-                VestingClaimed {
-                    grant_id,
-                    beneficiary: beneficiary.clone(),
-                    amount: claim_amount,
-                    claimed_at: current_time,
-                },
-            );
+        Parameters {
+            min_claim: config.min_claim,
+            max_schedules_per_user: config.max_schedules_per_user,
+            commitment_cap: config.commitment_cap,
+            default_cooldown: config.default_cooldown,
+            transfer_veto_window: config.transfer_veto_window,
+            governance_timelock: config.governance_timelock,
+            default_cliff_bps: config.default_cliff_bps,
+            claim_fee_bps: config.claim_fee_bps,
+            grant_cancellation_window: config.grant_cancellation_window,
+            rounding_mode: config.rounding_mode,
         }
+    }
 
-        persist_schedules(&env, &schedules);
+    /// Timestamp of the schedule's next state change: start, cliff end, or full vesting.
+    ///
+    /// Returns `None` for claimed, revoked, fully vested, or not-yet-started schedules.
+    pub fn next_event_at(env: Env, grant_id: u64) -> Result<Option<u64>, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
 
-        Ok(total_claimable)
+        Ok(next_event(&env, &load_config(&env), &schedule))
     }
 
-    /// Revoke a vesting schedule (governance/admin only, with timelock)
-    pub fn revoke(
-        env: Env,
-        grant_id: u64,
-        admin: Address,
-        revoke_delay: u64,
-    ) -> Result<(), VestingError> {
-        admin.require_auth();
+    /// Total claimed across a user's stored schedules, revoked ones included.
+    ///
+    /// Schedules deleted by finalization no longer count.
+    pub fn total_claimed_by(env: Env, user: Address) -> i128 {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
 
-        // Check pause state via CircuitBreaker
-        CircuitBreaker::require_not_paused(&env, symbol_short!("revoke"));
+        user_schedule_ids(&env, &user)
+            .iter()
+            .filter_map(|grant_id| schedules.get(grant_id))
+            .map(|schedule| schedule.claimed_amount)
+            .sum()
+    }
 
-        // Verify caller is admin
-        let admin_key = symbol_short!("admin");
-        let stored_admin: Address = env
+    /// Earliest `next_event_at` across a user's schedules (`None` if all are terminal)
+    pub fn user_next_unlock(env: Env, user: Address) -> Option<u64> {
+        let schedules: soroban_sdk::Map<u64, VestingSchedule> = env
             .storage()
             .persistent()
-            .get(&admin_key)
-            .ok_or(VestingError::Unauthorized)?;
+            .get(&symbol_short!("sched"))
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+        let config = load_config(&env);
 
-        if admin != stored_admin {
-            return Err(VestingError::Unauthorized);
+        user_schedule_ids(&env, &user)
+            .iter()
+            .filter_map(|grant_id| schedules.get(grant_id))
+            .filter_map(|schedule| next_event(&env, &config, &schedule))
+            .min()
+    }
+
+    /// Seconds left until a schedule is fully vested, stretched by any halting pause.
+    ///
+    /// Terminal schedules report 0; schedules awaiting acceptance or a milestone report
+    /// their full duration.
+    pub fn time_until_fully_vested(env: Env, grant_id: u64) -> Result<u64, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        if schedule.claimed || schedule.revoked {
+            return Ok(0);
+        }
+        if awaiting_start(&schedule) {
+            return Ok(schedule.duration);
         }
 
-        // Get vesting schedule
-        let mut schedules = load_schedules(&env)?;
+        let config = load_config(&env);
+        let clock = vesting_clock(&env, &config, &schedule);
 
-        let mut schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+        Ok(schedule
+            .start_time
+            .saturating_add(schedule.duration)
+            .saturating_sub(clock))
+    }
 
-        // Cannot revoke already claimed
-        if schedule.claimed {
-            return Err(VestingError::AlreadyClaimed);
+    /// Average amount vesting per day over the full schedule duration.
+    ///
+    /// Instantaneous schedules (zero duration) report the full amount.
+    pub fn average_daily_accrual(env: Env, grant_id: u64) -> Result<i128, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        if schedule.duration == 0 {
+            return Ok(schedule.amount);
         }
 
-        // Cannot revoke already revoked
-        if schedule.revoked {
-            return Err(VestingError::Revoked);
+        let daily = (schedule.amount as u128 * SECONDS_PER_DAY as u128) / schedule.duration as u128;
+
+        Ok(daily as i128)
+    }
+
+    /// Current vesting rate in base units per second: unvested amount over time left.
+    ///
+    /// Follows the adjusted timeline, so it is 0 while a halting pause is in progress and
+    /// already reflects a cliff having passed. Terminal and fully vested schedules report 0.
+    pub fn effective_rate(env: Env, grant_id: u64) -> Result<i128, VestingError> {
+        let schedules = load_schedules(&env)?;
+        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
+
+        let config = load_config(&env);
+        let frozen =
+            config.pause_halts_vesting && env.storage().instance().has(&symbol_short!("paused_at"));
+        if schedule.claimed || schedule.revoked || frozen {
+            return Ok(0);
         }
 
-        // Enforce timelock for revocation (minimum 1 hour)
-        if revoke_delay < 3600 {
-            return Err(VestingError::InvalidTimelock);
+        let clock = vesting_clock(&env, &config, &schedule);
+        let remaining_time = if awaiting_start(&schedule) {
+            schedule.duration
+        } else {
+            schedule
+                .start_time
+                .saturating_add(schedule.duration)
+                .saturating_sub(clock)
+        };
+        if remaining_time == 0 {
+            return Ok(0);
         }
 
-        // Check if enough time has passed since grant to allow revocation
+        let vested = Self::calculate_vested_amount(&schedule, clock, config.rounding_mode)?;
+
+        Ok((schedule.amount - vested) / remaining_time as i128)
+    }
+
+    /// Shared tail of `revoke` and `revoke_future_tranches`: keep what has vested for the
+    /// beneficiary, return the rest to the treasury and mark the schedule revoked
+    fn revoke_unvested(
+        env: &Env,
+        admin: Address,
+        grant_id: u64,
+        mut schedules: soroban_sdk::Map<u64, VestingSchedule>,
+        mut schedule: VestingSchedule,
+        not_started: bool,
+    ) -> Result<(), VestingError> {
         let current_time = env.ledger().timestamp();
-        if current_time < schedule.start_time + revoke_delay {
-            return Err(VestingError::NotEnoughTimeForRevoke);
+
+        // Tokens vested so far are retained for the beneficiary
+        let vested = if not_started {
+            0
+        } else {
+            let config = load_config(env);
+            Self::calculate_vested_amount(
+                &schedule,
+                vesting_clock(env, &config, &schedule),
+                config.rounding_mode,
+            )?
+        };
+        let retained = (vested - schedule.claimed_amount).max(0);
+        let forfeited = schedule.amount - schedule.claimed_amount - retained;
+
+        // The forfeited tokens go back to the treasury, less whatever the retained share
+        // needs out of a partially funded schedule; the balance is checked before any
+        // state changes so a drained contract fails the revoke instead of half-applying it
+        let token = read_token(env).ok_or(VestingError::TokenNotSet)?;
+        let treasury = read_treasury(env).ok_or(VestingError::GovernanceNotSet)?;
+        let token_client = soroban_sdk::token::Client::new(env, &token);
+        let returned =
+            (backed_refund(env, grant_id, &schedule, retained + forfeited) - retained).max(0);
+        if token_client.balance(&env.current_contract_address()) < returned {
+            return Err(VestingError::InsufficientContractBalance);
+        }
+        if returned > 0 {
+            token_client.transfer(&env.current_contract_address(), &treasury, &returned);
+        }
+        if not_started {
+            env.events()
+                .publish((symbol_short!("rvk_full"),), (grant_id, returned, 0i128));
+        }
+        if retained > 0 {
+            env.storage()
+                .persistent()
+                .set(&AcademyDataKey::VestedAtRevoke(grant_id), &vested);
         }
 
         // Mark as revoked
         schedule.revoked = true;
         schedule.revoke_time = current_time;
+        assert_schedule_invariants(&schedule)?;
+        adjust_totals(env, 0, 0, -forfeited);
+        add_reclaimed(env, returned);
         schedules.set(grant_id, schedule.clone());
-        persist_schedules(&env, &schedules);
+        persist_schedules(env, &schedules);
+        update_active(env, grant_id, false);
+
+        notify_hooks(
+            env,
+            HookCall::Revoke,
+            grant_id,
+            &schedule.beneficiary,
+            forfeited,
+        );
 
         // Emit revoke event
         let revoke_event = RevokeEvent {
@@ -506,86 +5466,201 @@ impl AcademyVestingContract {
         Ok(())
     }
 
-    /// Query vesting schedule details
-    pub fn get_vesting(env: Env, grant_id: u64) -> Result<VestingSchedule, VestingError> {
-        let schedules = load_schedules(&env)?;
-
-        schedules.get(grant_id).ok_or(VestingError::GrantNotFound)
-    }
-
-    /// Calculate vested amount at current time
-    pub fn get_vested_amount(env: Env, grant_id: u64) -> Result<i128, VestingError> {
-        let schedules = load_schedules(&env)?;
-
-        let schedule = schedules.get(grant_id).ok_or(VestingError::GrantNotFound)?;
-
-        let current_time = env.ledger().timestamp();
-        Self::calculate_vested_amount(&schedule, current_time)
-    }
-
     /// Internal helper: calculate vested amount based on schedule and current time
     fn calculate_vested_amount(
         schedule: &VestingSchedule,
         current_time: u64,
+        rounding: RoundingMode,
     ) -> Result<i128, VestingError> {
+        // Acceptance- and milestone-gated grants have not started yet
+        if awaiting_start(schedule) {
+            return Ok(0);
+        }
+
         // If not started yet
         if current_time < schedule.start_time {
             return Ok(0);
         }
 
+        // Degenerate start == end schedules vest in full at start (no division below)
+        if schedule.duration == 0 {
+            return Ok(schedule.amount);
+        }
+
+        // Work in elapsed time so far-future starts can never wrap or overflow
+        let elapsed = current_time.saturating_sub(schedule.start_time);
+
         // If cliff hasn't passed
-        if current_time < schedule.start_time + schedule.cliff {
+        if elapsed < schedule.cliff {
             return Ok(0);
         }
 
         // If fully vested
-        if current_time >= schedule.start_time + schedule.duration {
+        if elapsed >= schedule.duration {
             return Ok(schedule.amount);
         }
 
         // Partial vesting (linear vesting after cliff)
-        let vested_duration = current_time - (schedule.start_time + schedule.cliff);
+        let vested_duration = elapsed - schedule.cliff;
         let remaining_duration = schedule.duration - schedule.cliff;
 
         if remaining_duration == 0 {
             return Ok(schedule.amount);
         }
 
+        // The tail share only unlocks at the end; the rest vests along the curve
+        let tail = schedule.amount as u128 * schedule.tail_bps as u128 / BPS_DENOMINATOR as u128;
+        let linear = schedule.amount as u128 - tail;
+
+        // Accruing curves fall back to linear if a timeline change pushed growth past the cap
+        if let VestingCurve::Accruing(apy_bps) = schedule.curve {
+            if let Some(fraction) = accrued_fraction(apy_bps, vested_duration, remaining_duration) {
+                let remainder = (linear % ACCRUAL_SCALE) * fraction;
+                let vested_amount = linear / ACCRUAL_SCALE * fraction
+                    + match rounding {
+                        RoundingMode::Floor => remainder / ACCRUAL_SCALE,
+                        RoundingMode::Ceil => remainder.div_ceil(ACCRUAL_SCALE),
+                    };
+                return Ok(vested_amount as i128);
+            }
+        }
+
+        // A tranche unlocks whole once its share of the span has passed
+        if let VestingCurve::Stepped(tranches) = schedule.curve {
+            if tranches > 0 {
+                let reached =
+                    vested_duration as u128 * tranches as u128 / remaining_duration as u128;
+                let numerator = linear * reached;
+                let vested_amount = match rounding {
+                    RoundingMode::Floor => numerator / tranches as u128,
+                    RoundingMode::Ceil => numerator.div_ceil(tranches as u128),
+                };
+                return Ok(vested_amount as i128);
+            }
+        }
+
         // Use fixed-point arithmetic to avoid floating point
-        let vested_amount =
-            (schedule.amount as u128 * vested_duration as u128) / remaining_duration as u128;
+        let numerator = linear * vested_duration as u128;
+        let vested_amount = match rounding {
+            RoundingMode::Floor => numerator / remaining_duration as u128,
+            RoundingMode::Ceil => numerator.div_ceil(remaining_duration as u128),
+        };
 
         Ok(vested_amount as i128)
     }
 
     /// Get contract information
     pub fn get_info(env: Env) -> Result<(Address, Address, Address), VestingError> {
-        let admin_key = symbol_short!("admin");
-        let token_key = symbol_short!("token");
-        let gov_key = symbol_short!("gov");
+        let admin = read_admin(&env).ok_or(VestingError::AdminNotSet)?;
+        let token = read_token(&env).ok_or(VestingError::TokenNotSet)?;
+        let governance = read_governance(&env).ok_or(VestingError::GovernanceNotSet)?;
 
-        let admin = env
-            .storage()
-            .persistent()
-            .get(&admin_key)
-            .ok_or(VestingError::Unauthorized)?;
+        Ok((admin, token, governance))
+    }
 
-        let token = env
-            .storage()
-            .persistent()
-            .get(&token_key)
-            .ok_or(VestingError::Unauthorized)?;
+    /// Query the storage layout version (1 for deployments predating versioning)
+    pub fn get_storage_version(env: Env) -> u32 {
+        storage_version(&env)
+    }
 
-        let governance = env
-            .storage()
-            .persistent()
-            .get(&gov_key)
-            .ok_or(VestingError::Unauthorized)?;
+    /// Report what `migrate_storage` would do without writing any storage
+    pub fn preview_migration(env: Env) -> MigrationReport {
+        let persistent = env.storage().persistent();
+        let [admin, token, governance, counter] =
+            legacy_core_keys().map(|(legacy, _)| persistent.has(&legacy));
 
-        Ok((admin, token, governance))
+        MigrationReport {
+            current_version: storage_version(&env),
+            target_version: STORAGE_VERSION,
+            legacy_admin: admin,
+            legacy_token: token,
+            legacy_governance: governance,
+            legacy_counter: counter,
+            keys_to_migrate: [admin, token, governance, counter]
+                .iter()
+                .filter(|present| **present)
+                .count() as u32,
+            legacy_schedules: legacy_schedules(&env).len(),
+        }
+    }
+
+    /// Whether `migrate_storage` would keep every value.
+    ///
+    /// False when a legacy key and its v2 counterpart are both present: migration keeps the
+    /// v2 value and deletes the legacy one, so one of the two would be lost.
+    pub fn migration_is_lossless(env: Env) -> bool {
+        legacy_core_keys().iter().all(|(legacy, key)| {
+            !(env.storage().persistent().has(legacy) && env.storage().instance().has(key))
+        })
+    }
+
+    /// Move legacy v1 core keys into the v2 instance layout and rewrite v1 schedules in the
+    /// current layout (admin only).
+    ///
+    /// Returns the number of keys and schedules migrated. Existing v2 values are never
+    /// overwritten.
+    pub fn migrate_storage(env: Env, admin: Address) -> Result<u32, VestingError> {
+        admin.require_auth();
+
+        require_admin(&env, &admin, "migrate_storage")?;
+        log_admin_action(&env, &admin, "migrate_storage");
+
+        let from_version = storage_version(&env);
+        let mut migrated = 0u32;
+        for (legacy, key) in legacy_core_keys() {
+            let value: Option<Val> = env.storage().persistent().get(&legacy);
+            if let Some(value) = value {
+                if !env.storage().instance().has(&key) {
+                    env.storage().instance().set(&key, &value);
+                }
+                env.storage().persistent().remove(&legacy);
+                migrated += 1;
+            }
+        }
+        let granted_by = read_admin(&env).unwrap_or_else(|| admin.clone());
+        migrated += migrate_legacy_schedules(&env, &granted_by);
+
+        env.storage()
+            .instance()
+            .set(&AcademyDataKey::StorageVersion, &STORAGE_VERSION);
+        if from_version != STORAGE_VERSION {
+            let mut history = Self::get_version_history(env.clone());
+            history.push_back((STORAGE_VERSION, env.ledger().timestamp()));
+            env.storage()
+                .persistent()
+                .set(&AcademyDataKey::VersionHistory, &history);
+        }
+        env.events().publish(
+            (symbol_short!("migrated"),),
+            (from_version, STORAGE_VERSION, migrated),
+        );
+
+        Ok(migrated)
+    }
+
+    /// Storage versions this contract has migrated to, as `(version, migrated_at)` in order
+    pub fn get_version_history(env: Env) -> Vec<(u32, u64)> {
+        env.storage()
+            .persistent()
+            .get(&AcademyDataKey::VersionHistory)
+            .unwrap_or_else(|| Vec::new(&env))
     }
 
     pub fn max_batch_claims() -> u32 {
         MAX_BATCH_CLAIMS
     }
 }
+
+#[cfg(any(test, feature = "testutils"))]
+#[contractimpl]
+impl AcademyVestingContract {
+    /// Pin the timestamp used by vesting math (test builds only)
+    pub fn set_mock_time(env: Env, ts: u64) {
+        env.storage().instance().set(&AcademyDataKey::MockTime, &ts);
+    }
+
+    /// Return vesting math to the ledger timestamp (test builds only)
+    pub fn clear_mock_time(env: Env) {
+        env.storage().instance().remove(&AcademyDataKey::MockTime);
+    }
+}
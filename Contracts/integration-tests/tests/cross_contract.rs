@@ -3,6 +3,7 @@
 extern crate std;
 
 use academy_rewards::AcademyRewardsContract;
+use academy_vesting::AcademyVestingContract;
 use messaging::UpgradeableMessagingContract;
 use shared::circuit_breaker::CircuitBreakerConfig;
 use shared::governance::ProposalStatus;
@@ -10,7 +11,7 @@ use social_rewards::SocialRewardsContract;
 use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short,
     testutils::{Address as _, Ledger},
-    token, Address, Env, String, Vec,
+    token, xdr, Address, Bytes, Env, String, TryFromVal, Vec,
 };
 use trading::UpgradeableTradingContract;
 
@@ -269,3 +270,88 @@ fn test_shared_governance_module_across_contracts() {
     assert_eq!(trade_status, ProposalStatus::Approved);
     assert_eq!(msg_status, ProposalStatus::Approved);
 }
+
+/// Deploy the Stellar Asset Contract wrapping native XLM
+fn register_native_asset(env: &Env) -> Address {
+    // XDR-serialized `Asset::Native` is just its 4-byte zero discriminant
+    env.deployer()
+        .with_stellar_asset(Bytes::from_array(env, &[0u8; 4]))
+        .deploy()
+}
+
+/// Create a classic account holding `balance` stroops; the native SAC has no admin to mint
+fn create_funded_account(env: &Env, balance: i64) -> Address {
+    let account_id = xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(
+        [7u8; 32],
+    )));
+    let key = std::rc::Rc::new(xdr::LedgerKey::Account(xdr::LedgerKeyAccount {
+        account_id: account_id.clone(),
+    }));
+    let entry = std::rc::Rc::new(xdr::LedgerEntry {
+        data: xdr::LedgerEntryData::Account(xdr::AccountEntry {
+            account_id: account_id.clone(),
+            balance,
+            flags: 0,
+            home_domain: Default::default(),
+            inflation_dest: None,
+            num_sub_entries: 0,
+            seq_num: xdr::SequenceNumber(0),
+            thresholds: xdr::Thresholds([1; 4]),
+            signers: xdr::VecM::default(),
+            ext: xdr::AccountEntryExt::V0,
+        }),
+        last_modified_ledger_seq: 0,
+        ext: xdr::LedgerEntryExt::V0,
+    });
+
+    let budget = env.host().budget_cloned();
+    env.host()
+        .with_mut_storage(|storage| storage.put(&key, &entry, None, &budget))
+        .unwrap();
+
+    Address::try_from_val(env, &xdr::ScAddress::Account(account_id)).unwrap()
+}
+
+#[test]
+fn test_academy_vesting_grants_and_claims_native_xlm() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let xlm_id = register_native_asset(&env);
+    let xlm = token::Client::new(&env, &xlm_id);
+    let funder = create_funded_account(&env, 10_000_000);
+
+    let vesting_id = env.register_contract(None, AcademyVestingContract);
+    let vesting = academy_vesting::vesting::AcademyVestingContractClient::new(&env, &vesting_id);
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let cb_config = CircuitBreakerConfig {
+        max_volume_per_period: 10_000_000,
+        max_tx_count_per_period: 100,
+        period_duration: 3600,
+    };
+    vesting.init(&admin, &xlm_id, &governance, &cb_config);
+    assert_eq!(vesting.get_token_decimals(), 7);
+
+    // Stipends are funded by transfer from a classic account
+    xlm.transfer(&funder, &vesting_id, &3_000_000);
+
+    let claimed = vesting.grant_vesting(&admin, &beneficiary, &1_000_000, &0, &0, &1000);
+    let revoked = vesting.grant_vesting(&admin, &beneficiary, &2_000_000, &5000, &0, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    let receipt = vesting.claim(&claimed, &beneficiary);
+    assert_eq!(receipt.amount, 500_000);
+    assert_eq!(xlm.balance(&beneficiary), 500_000);
+
+    // Revoking before start sends the whole grant back to the treasury
+    vesting.revoke(&revoked, &admin, &3600);
+    assert_eq!(xlm.balance(&governance), 2_000_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    vesting.claim(&claimed, &beneficiary);
+    assert_eq!(xlm.balance(&beneficiary), 1_000_000);
+    assert_eq!(xlm.balance(&vesting_id), 0);
+    assert_eq!(xlm.balance(&funder), 7_000_000);
+}